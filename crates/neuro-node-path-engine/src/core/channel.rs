@@ -15,7 +15,7 @@ pub struct NeuralChannel {
     pub active: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum SignalType {
     DataFlow,
     ControlFlow,
@@ -25,6 +25,19 @@ pub enum SignalType {
     Custom(String),
 }
 
+impl std::fmt::Display for SignalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignalType::DataFlow => write!(f, "data_flow"),
+            SignalType::ControlFlow => write!(f, "control_flow"),
+            SignalType::DependencyLink => write!(f, "dependency_link"),
+            SignalType::CallGraph => write!(f, "call_graph"),
+            SignalType::Bidirectional => write!(f, "bidirectional"),
+            SignalType::Custom(s) => write!(f, "{}", s),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelMetadata {
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -64,7 +77,7 @@ impl NeuralChannel {
     }
 
     pub fn set_weight(&mut self, weight: f64) {
-        self.weight = weight.max(0.0).min(1.0);
+        self.weight = weight.clamp(0.0, 1.0);
     }
 
     pub fn deactivate(&mut self) {
@@ -11,9 +11,12 @@ pub struct Cluster {
     pub cohesion_score: f64,
     pub cluster_type: ClusterType,
     pub metadata: ClusterMetadata,
+    /// Repository namespace the cluster was formed in, if any.
+    #[serde(default)]
+    pub repo_id: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ClusterType {
     Functional,
     Architectural,
@@ -50,6 +53,7 @@ impl Cluster {
                 member_count: 0,
                 properties: HashMap::new(),
             },
+            repo_id: None,
         }
     }
 
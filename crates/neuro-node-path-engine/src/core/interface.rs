@@ -11,6 +11,9 @@ pub struct Interface {
     pub input_schema: serde_json::Value,
     pub output_schema: serde_json::Value,
     pub metadata: InterfaceMetadata,
+    /// Node whose source declares this interface, for discovered interfaces.
+    #[serde(default)]
+    pub source_node_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -23,6 +26,28 @@ pub enum InterfaceType {
     Custom(String),
 }
 
+impl InterfaceType {
+    /// Guesses the interface type from a module or trait name, falling back
+    /// to `Custom("api")` when no keyword matches.
+    pub fn from_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        let has = |keywords: &[&str]| keywords.iter().any(|k| name.contains(k));
+        if has(&["query", "search", "find", "lookup"]) {
+            InterfaceType::QueryInterface
+        } else if has(&["audit", "log", "trail"]) {
+            InterfaceType::AuditInterface
+        } else if has(&["valid", "verify", "check"]) {
+            InterfaceType::ValidationInterface
+        } else if has(&["cluster"]) {
+            InterfaceType::ClusterInterface
+        } else if has(&["channel", "signal"]) {
+            InterfaceType::ChannelInterface
+        } else {
+            InterfaceType::Custom("api".to_string())
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MethodSignature {
     pub name: String,
@@ -31,6 +56,37 @@ pub struct MethodSignature {
     pub description: Option<String>,
 }
 
+impl MethodSignature {
+    /// `name(a: T, b: U) -> R`
+    pub fn render(&self) -> String {
+        let parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|(name, ty)| format!("{}: {}", name, ty))
+            .collect();
+        format!("{}({}) -> {}", self.name, parameters.join(", "), self.return_type)
+    }
+}
+
+/// Result of comparing an interface against an earlier version of itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compatibility {
+    Identical,
+    /// Only additions; existing callers keep working.
+    Compatible { added_methods: Vec<String> },
+    Breaking(Vec<Incompatibility>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Incompatibility {
+    MethodRemoved { name: String },
+    SignatureChanged { name: String, before: String, after: String },
+    /// The input schema requires a property callers did not have to send.
+    InputPropertyRequired { property: String },
+    /// The output schema no longer has a property callers may read.
+    OutputPropertyRemoved { property: String },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceMetadata {
     pub version: String,
@@ -52,6 +108,62 @@ impl Interface {
                 created_at: chrono::Utc::now(),
                 properties: HashMap::new(),
             },
+            source_node_id: None,
+        }
+    }
+
+    /// Compares `self`, a newer version, against `previous`. Methods are
+    /// matched by name; added methods are compatible, removed methods and
+    /// changed parameters or return types are breaking, as are newly required
+    /// input properties and removed output properties.
+    pub fn is_compatible_with(&self, previous: &Interface) -> Compatibility {
+        let mut incompatibilities = Vec::new();
+        for old in &previous.exposed_methods {
+            match self.exposed_methods.iter().find(|m| m.name == old.name) {
+                None => incompatibilities.push(Incompatibility::MethodRemoved {
+                    name: old.name.clone(),
+                }),
+                Some(new) => {
+                    if new.parameters != old.parameters || new.return_type != old.return_type {
+                        incompatibilities.push(Incompatibility::SignatureChanged {
+                            name: old.name.clone(),
+                            before: old.render(),
+                            after: new.render(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let previously_required = schema_required(&previous.input_schema);
+        for property in schema_required(&self.input_schema) {
+            if !previously_required.contains(&property) {
+                incompatibilities.push(Incompatibility::InputPropertyRequired { property });
+            }
+        }
+        let current_outputs = schema_properties(&self.output_schema);
+        for property in schema_properties(&previous.output_schema) {
+            if !current_outputs.contains(&property) {
+                incompatibilities.push(Incompatibility::OutputPropertyRemoved { property });
+            }
+        }
+
+        if !incompatibilities.is_empty() {
+            return Compatibility::Breaking(incompatibilities);
+        }
+        let added_methods: Vec<String> = self
+            .exposed_methods
+            .iter()
+            .filter(|m| !previous.exposed_methods.iter().any(|old| old.name == m.name))
+            .map(|m| m.name.clone())
+            .collect();
+        if added_methods.is_empty()
+            && self.input_schema == previous.input_schema
+            && self.output_schema == previous.output_schema
+        {
+            Compatibility::Identical
+        } else {
+            Compatibility::Compatible { added_methods }
         }
     }
 
@@ -69,3 +181,23 @@ impl Interface {
         self
     }
 }
+
+/// Entries of a JSON Schema's `required` array.
+fn schema_required(schema: &serde_json::Value) -> Vec<String> {
+    schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Keys of a JSON Schema's `properties` object, sorted.
+fn schema_properties(schema: &serde_json::Value) -> Vec<String> {
+    let mut properties: Vec<String> = schema["properties"]
+        .as_object()
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default();
+    properties.sort();
+    properties
+}
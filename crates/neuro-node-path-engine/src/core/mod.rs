@@ -3,9 +3,11 @@ pub mod node;
 pub mod cluster;
 pub mod channel;
 pub mod interface;
+pub mod stub;
 
 pub use neuron::Neuron;
 pub use node::Node;
 pub use cluster::Cluster;
 pub use channel::NeuralChannel;
 pub use interface::Interface;
+pub use stub::StubLanguage;
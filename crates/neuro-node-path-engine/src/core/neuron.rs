@@ -38,11 +38,16 @@ impl Neuron {
     }
 
     pub fn fire(&mut self, signal_strength: f64) -> f64 {
-        self.activation_level = (self.activation_level + signal_strength).min(1.0).max(0.0);
+        self.activation_level = (self.activation_level + signal_strength).clamp(0.0, 1.0);
         self.last_fired = Some(Utc::now());
         self.apply_activation_function()
     }
 
+    /// Current output without firing.
+    pub fn output(&self) -> f64 {
+        self.apply_activation_function()
+    }
+
     fn apply_activation_function(&self) -> f64 {
         1.0 / (1.0 + (-self.activation_level).exp())
     }
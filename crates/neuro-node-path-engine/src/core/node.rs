@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use crate::utils::normalize_name;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Node {
     pub id: String,
     pub name: String,
+    /// `name` as split and case-folded by [`crate::utils::normalize_name`].
+    #[serde(default)]
+    pub normalized_name: String,
     pub node_type: NodeType,
     pub source_path: String,
     pub hash: String,
@@ -13,6 +17,12 @@ pub struct Node {
     pub parent_id: Option<String>,
     pub children: HashSet<String>,
     pub metadata: NodeMetadata,
+    /// Repository namespace the node was indexed under, if any.
+    #[serde(default)]
+    pub repo_id: Option<String>,
+    /// Coordinates from the last [`crate::NeuroNodePathEngine::compute_layout`].
+    #[serde(default)]
+    pub position: Option<[f64; 3]>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -25,22 +35,27 @@ pub enum NodeType {
     Protocol,
     Model,
     Interface,
+    Config,
     Custom(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NodeMetadata {
     pub language: String,
     pub signature: Option<String>,
     pub documentation: Option<String>,
     pub dependencies: Vec<String>,
     pub properties: HashMap<String, serde_json::Value>,
+    /// Free-form labels such as `hotpath` or `owned-by-team-x`.
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
 }
 
 impl Node {
     pub fn new(name: String, node_type: NodeType, source_path: String) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
+            normalized_name: normalize_name(&name),
             name,
             node_type,
             source_path,
@@ -54,7 +69,10 @@ impl Node {
                 documentation: None,
                 dependencies: Vec::new(),
                 properties: HashMap::new(),
+                tags: BTreeSet::new(),
             },
+            repo_id: None,
+            position: None,
         }
     }
 
@@ -0,0 +1,449 @@
+use crate::core::Interface;
+use serde_json::Value;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Target languages for [`Interface::generate_stub`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StubLanguage {
+    Rust,
+    TypeScript,
+}
+
+impl FromStr for StubLanguage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Ok(StubLanguage::Rust),
+            "typescript" | "ts" => Ok(StubLanguage::TypeScript),
+            other => anyhow::bail!("Unknown stub language '{}'; expected rust or typescript", other),
+        }
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "static", "struct", "super", "trait", "type", "unsafe", "use",
+    "where", "while",
+];
+
+impl Interface {
+    /// Client source for this interface in `language`.
+    ///
+    /// Each exposed method becomes a client method with the same
+    /// parameters, which sends its arguments as a JSON object through a
+    /// caller-supplied transport and decodes the reply as the return type.
+    /// The input and output schemas' properties become `<Name>Input` and
+    /// `<Name>Output` types. Types are translated best-effort: references
+    /// become owned types and `Result<T, _>` becomes `T`; anything the
+    /// generator does not know is `serde_json::Value` in Rust and `unknown`
+    /// in TypeScript.
+    pub fn generate_stub(&self, language: StubLanguage) -> String {
+        match language {
+            StubLanguage::Rust => self.rust_stub(),
+            StubLanguage::TypeScript => self.typescript_stub(),
+        }
+    }
+
+    fn rust_stub(&self) -> String {
+        let name = pascal_case(&self.name);
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "// Generated client for interface `{}` (version {}).",
+            self.name, self.metadata.version
+        );
+        for (suffix, schema) in [("Input", &self.input_schema), ("Output", &self.output_schema)] {
+            let fields = schema_fields(schema);
+            if fields.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out);
+            let _ = writeln!(out, "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]");
+            let _ = writeln!(out, "pub struct {}{} {{", name, suffix);
+            for (field, property, required) in fields {
+                let ident = rust_ident(&field);
+                if ident.trim_start_matches("r#") != field {
+                    let _ = writeln!(out, "    #[serde(rename = \"{}\")]", field.escape_default());
+                }
+                let ty = rust_schema_type(property);
+                let ty = if required { ty } else { format!("Option<{}>", ty) };
+                let _ = writeln!(out, "    pub {}: {},", ident, ty);
+            }
+            let _ = writeln!(out, "}}");
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "/// Sends one call to `{}`; `method` is the exposed method name.",
+            self.name
+        );
+        let _ = writeln!(out, "pub trait {}Transport {{", name);
+        let _ = writeln!(
+            out,
+            "    fn call(&self, method: &str, args: serde_json::Value) -> anyhow::Result<serde_json::Value>;"
+        );
+        let _ = writeln!(out, "}}");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "pub struct {}Client<T: {}Transport> {{", name, name);
+        let _ = writeln!(out, "    transport: T,");
+        let _ = writeln!(out, "}}");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "impl<T: {}Transport> {}Client<T> {{", name, name);
+        let _ = writeln!(out, "    pub fn new(transport: T) -> Self {{");
+        let _ = writeln!(out, "        Self {{ transport }}");
+        let _ = writeln!(out, "    }}");
+        for method in &self.exposed_methods {
+            let parameters: Vec<(String, String)> = method
+                .parameters
+                .iter()
+                .map(|(param, ty)| (param_name(param), ty.clone()))
+                .collect();
+            let signature: Vec<String> = std::iter::once("&self".to_string())
+                .chain(
+                    parameters
+                        .iter()
+                        .map(|(param, ty)| format!("{}: {}", rust_ident(param), rust_owned(ty))),
+                )
+                .collect();
+            let args: Vec<String> = parameters
+                .iter()
+                .map(|(param, _)| format!("\"{}\": {}", param, rust_ident(param)))
+                .collect();
+
+            let _ = writeln!(out);
+            if let Some(description) = &method.description {
+                let _ = writeln!(out, "    /// {}", description);
+            }
+            let _ = writeln!(
+                out,
+                "    pub fn {}({}) -> anyhow::Result<{}> {{",
+                rust_ident(&method.name),
+                signature.join(", "),
+                rust_return(&method.return_type)
+            );
+            let _ = writeln!(
+                out,
+                "        let args = serde_json::json!({{ {} }});",
+                args.join(", ")
+            );
+            let _ = writeln!(
+                out,
+                "        Ok(serde_json::from_value(self.transport.call(\"{}\", args)?)?)",
+                method.name
+            );
+            let _ = writeln!(out, "    }}");
+        }
+        let _ = writeln!(out, "}}");
+        out
+    }
+
+    fn typescript_stub(&self) -> String {
+        let name = pascal_case(&self.name);
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "// Generated client for interface `{}` (version {}).",
+            self.name, self.metadata.version
+        );
+        for (suffix, schema) in [("Input", &self.input_schema), ("Output", &self.output_schema)] {
+            let fields = schema_fields(schema);
+            if fields.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out);
+            let _ = writeln!(out, "export interface {}{} {{", name, suffix);
+            for (field, property, required) in fields {
+                let key = if is_identifier(&field) {
+                    field
+                } else {
+                    format!("\"{}\"", field.escape_default())
+                };
+                let optional = if required { "" } else { "?" };
+                let _ = writeln!(out, "  {}{}: {};", key, optional, ts_schema_type(property));
+            }
+            let _ = writeln!(out, "}}");
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "/** Sends one call to `{}`; `method` is the exposed method name. */",
+            self.name
+        );
+        let _ = writeln!(
+            out,
+            "export type {}Transport = (method: string, args: Record<string, unknown>) => Promise<unknown>;",
+            name
+        );
+        let _ = writeln!(out);
+        let _ = writeln!(out, "export class {}Client {{", name);
+        let _ = writeln!(out, "  constructor(private readonly transport: {}Transport) {{}}", name);
+        for method in &self.exposed_methods {
+            let parameters: Vec<(String, String)> = method
+                .parameters
+                .iter()
+                .map(|(param, ty)| (param_name(param), ty.clone()))
+                .collect();
+            let signature: Vec<String> = parameters
+                .iter()
+                .map(|(param, ty)| format!("{}: {}", camel_case(param), ts_type(ty)))
+                .collect();
+            let args: Vec<String> = parameters
+                .iter()
+                .map(|(param, _)| format!("\"{}\": {}", param, camel_case(param)))
+                .collect();
+            let args = if args.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", args.join(", "))
+            };
+
+            let _ = writeln!(out);
+            if let Some(description) = &method.description {
+                let _ = writeln!(out, "  /** {} */", description);
+            }
+            let return_type = ts_type(&method.return_type);
+            let _ = writeln!(
+                out,
+                "  async {}({}): Promise<{}> {{",
+                camel_case(&method.name),
+                signature.join(", "),
+                return_type
+            );
+            let _ = writeln!(
+                out,
+                "    return (await this.transport(\"{}\", {})) as {};",
+                method.name,
+                args,
+                return_type
+            );
+            let _ = writeln!(out, "  }}");
+        }
+        let _ = writeln!(out, "}}");
+        out
+    }
+}
+
+/// `(name, schema, required)` for each of a JSON Schema's properties,
+/// sorted by name.
+fn schema_fields(schema: &Value) -> Vec<(String, &Value, bool)> {
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let mut fields: Vec<(String, &Value, bool)> = schema["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, property)| (name.clone(), property, required.contains(&name.as_str())))
+        .collect();
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+    fields
+}
+
+fn rust_schema_type(property: &Value) -> String {
+    match property["type"].as_str() {
+        Some("string") => "String".to_string(),
+        Some("integer") => "i64".to_string(),
+        Some("number") => "f64".to_string(),
+        Some("boolean") => "bool".to_string(),
+        Some("array") => format!("Vec<{}>", rust_schema_type(&property["items"])),
+        _ => "serde_json::Value".to_string(),
+    }
+}
+
+fn ts_schema_type(property: &Value) -> String {
+    match property["type"].as_str() {
+        Some("string") => "string".to_string(),
+        Some("integer") | Some("number") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("array") => array_of(ts_schema_type(&property["items"])),
+        Some("object") => "Record<string, unknown>".to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Splits `Vec<Option<u8>>` into `("Vec", ["Option<u8>"])`, keeping only
+/// the last path segment of the base name.
+fn split_generic(ty: &str) -> (&str, Vec<&str>) {
+    let ty = ty.trim();
+    let (base, args) = match (ty.find('<'), ty.ends_with('>')) {
+        (Some(open), true) => (&ty[..open], split_top_level(&ty[open + 1..ty.len() - 1])),
+        _ => (ty, Vec::new()),
+    };
+    (base.rsplit("::").next().unwrap_or(base).trim(), args)
+}
+
+/// Splits on commas outside of brackets.
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, ch) in list.char_indices() {
+        match ch {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(list[start..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Drops `&`, `&mut` and lifetimes.
+fn strip_reference(ty: &str) -> &str {
+    let mut ty = ty.trim();
+    while let Some(rest) = ty.strip_prefix('&') {
+        ty = rest.trim_start();
+        if ty.starts_with('\'') {
+            ty = ty.split_once(' ').map(|(_, rest)| rest).unwrap_or("").trim_start();
+        }
+        ty = ty.strip_prefix("mut ").unwrap_or(ty).trim_start();
+    }
+    ty
+}
+
+/// An owned Rust type a client can deserialize into.
+fn rust_owned(ty: &str) -> String {
+    let ty = strip_reference(ty);
+    if ty.starts_with("impl ") || ty.starts_with("dyn ") || ty.is_empty() {
+        return "serde_json::Value".to_string();
+    }
+    if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return format!("Vec<{}>", rust_owned(inner));
+    }
+    if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let items: Vec<String> = split_top_level(inner).into_iter().map(rust_owned).collect();
+        return match items.len() {
+            0 => "()".to_string(),
+            1 => format!("({},)", items[0]),
+            _ => format!("({})", items.join(", ")),
+        };
+    }
+    let (base, args) = split_generic(ty);
+    match base {
+        "str" => "String".to_string(),
+        "Box" | "Arc" | "Rc" | "Cow" => match args.last() {
+            Some(inner) => rust_owned(inner),
+            None => "serde_json::Value".to_string(),
+        },
+        _ if args.is_empty() => ty.to_string(),
+        _ => {
+            let args: Vec<String> = args
+                .into_iter()
+                .filter(|arg| !arg.starts_with('\''))
+                .map(rust_owned)
+                .collect();
+            format!("{}<{}>", &ty[..ty.find('<').unwrap_or(ty.len())], args.join(", "))
+        }
+    }
+}
+
+/// [`rust_owned`], with `Result<T, _>` unwrapped to `T` because the client
+/// already returns a `Result`.
+fn rust_return(ty: &str) -> String {
+    let (base, args) = split_generic(strip_reference(ty));
+    match (base, args.first()) {
+        ("Result", Some(ok)) => rust_owned(ok),
+        _ => rust_owned(ty),
+    }
+}
+
+fn ts_type(ty: &str) -> String {
+    let ty = strip_reference(ty);
+    if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return array_of(ts_type(inner.split(';').next().unwrap_or(inner)));
+    }
+    if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let items: Vec<String> = split_top_level(inner).into_iter().map(ts_type).collect();
+        return if items.is_empty() {
+            "void".to_string()
+        } else {
+            format!("[{}]", items.join(", "))
+        };
+    }
+    let (base, args) = split_generic(ty);
+    let arg = |i: usize| args.get(i).map_or_else(|| "unknown".to_string(), |a| ts_type(a));
+    match base {
+        "" => "void".to_string(),
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+        | "u128" | "usize" | "f32" | "f64" => "number".to_string(),
+        "str" | "String" | "char" | "PathBuf" | "Path" | "Uuid" => "string".to_string(),
+        "bool" => "boolean".to_string(),
+        "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => array_of(arg(0)),
+        "Option" => format!("{} | null", arg(0)),
+        "HashMap" | "BTreeMap" => format!("Record<string, {}>", arg(1)),
+        "Result" | "Box" | "Arc" | "Rc" => arg(0),
+        "Cow" => arg(args.len().saturating_sub(1)),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn array_of(item: String) -> String {
+    if item.contains(' ') {
+        format!("({})[]", item)
+    } else {
+        format!("{}[]", item)
+    }
+}
+
+/// A parameter pattern such as `mut limit` as a plain name.
+fn param_name(pattern: &str) -> String {
+    pattern.trim().trim_start_matches("mut ").trim().to_string()
+}
+
+fn rust_ident(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else if ident.starts_with(|c: char| c.is_ascii_digit()) || ident.is_empty() {
+        format!("_{}", ident)
+    } else {
+        ident
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// `name` with every alphanumeric word capitalized and the rest dropped.
+fn join_words(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word[..1].to_ascii_uppercase() + &word[1..])
+        .collect()
+}
+
+fn pascal_case(name: &str) -> String {
+    let pascal = join_words(name);
+    if pascal.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        pascal
+    } else {
+        format!("Api{}", pascal)
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let joined = join_words(name);
+    if joined.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        joined[..1].to_ascii_lowercase() + &joined[1..]
+    } else {
+        format!("_{}", joined)
+    }
+}
@@ -1,7 +1,12 @@
 use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
 use hex;
+use tokio::sync::broadcast;
+
+/// Entries buffered per subscriber before the oldest are dropped.
+pub const DEFAULT_SUBSCRIBER_CAPACITY: usize = 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEntry {
@@ -11,60 +16,122 @@ pub struct AuditEntry {
     pub cluster_path: Vec<String>,
     pub hash: String,
     pub previous_hash: Option<String>,
+    /// Hex Ed25519 signature over `hash`, present when the trail has a signing key.
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Hex public key that produced `signature`.
+    #[serde(default)]
+    pub signer_public_key: Option<String>,
+    /// Free-form note about the signing key, e.g. when it was rotated.
+    #[serde(default)]
+    pub key_note: Option<String>,
+    /// Why the logged query's node path is partial, if it is.
+    #[serde(default)]
+    pub truncation: Option<String>,
 }
 
 pub struct AuditTrail {
     entries: Vec<AuditEntry>,
+    signing_key: Option<SigningKey>,
+    key_note: Option<String>,
+    /// Index of the first entry logged with a signing key configured.
+    signed_from: Option<usize>,
+    publisher: broadcast::Sender<AuditEntry>,
 }
 
 impl AuditTrail {
     pub fn new() -> Self {
+        Self::with_subscriber_capacity(DEFAULT_SUBSCRIBER_CAPACITY)
+    }
+
+    /// A trail whose subscribers each buffer up to `capacity` entries.
+    pub fn with_subscriber_capacity(capacity: usize) -> Self {
         Self {
             entries: Vec::new(),
+            signing_key: None,
+            key_note: None,
+            signed_from: None,
+            publisher: broadcast::channel(capacity).0,
         }
     }
 
+    /// Receives every entry logged from now on. Publishing never waits for
+    /// subscribers: one that falls more than the buffer capacity behind
+    /// loses the oldest entries, which [`AuditSubscription`] counts.
+    pub fn subscribe(&self) -> broadcast::Receiver<AuditEntry> {
+        self.publisher.subscribe()
+    }
+
+    /// Signs every subsequent entry with `key`. Calling this again rotates
+    /// the key; earlier entries keep the public key they were signed with.
+    /// From now on [`Self::verify_integrity`] rejects unsigned entries.
+    pub fn set_signing_key(&mut self, key: SigningKey, key_note: Option<String>) {
+        self.signing_key = Some(key);
+        self.key_note = key_note;
+        self.signed_from.get_or_insert(self.entries.len());
+    }
+
     pub fn log_query(
         &mut self,
         context: &crate::engine::QueryContext,
         node_path: &[String],
         cluster_path: &[String],
     ) -> anyhow::Result<Vec<String>> {
-        let previous_hash = self.entries.last().map(|e| e.hash.clone());
-
-        let entry_data = format!(
-            "{}:{}:{}",
-            context.timestamp,
-            node_path.join(","),
-            cluster_path.join(",")
-        );
-
-        let mut hasher = Sha256::new();
-        hasher.update(entry_data);
-        let hash = hex::encode(hasher.finalize());
+        self.log_partial_query(context, node_path, cluster_path, None)
+    }
 
-        let entry = AuditEntry {
+    /// Like [`Self::log_query`], recording `truncation` as the reason the
+    /// node path is incomplete. The reason is covered by the entry hash.
+    pub fn log_partial_query(
+        &mut self,
+        context: &crate::engine::QueryContext,
+        node_path: &[String],
+        cluster_path: &[String],
+        truncation: Option<&str>,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut entry = AuditEntry {
             timestamp: context.timestamp,
             query: context.query.clone(),
             node_path: node_path.to_vec(),
             cluster_path: cluster_path.to_vec(),
-            hash: hash.clone(),
-            previous_hash,
+            hash: String::new(),
+            previous_hash: self.entries.last().map(|e| e.hash.clone()),
+            signature: None,
+            signer_public_key: self
+                .signing_key
+                .as_ref()
+                .map(|key| hex::encode(key.verifying_key().to_bytes())),
+            key_note: self.key_note.clone(),
+            truncation: truncation.map(str::to_string),
         };
+        entry.hash = entry_hash(&entry);
+        entry.signature = self
+            .signing_key
+            .as_ref()
+            .map(|key| hex::encode(key.sign(entry.hash.as_bytes()).to_bytes()));
 
+        // Sending only fails when nobody is subscribed.
+        let _ = self.publisher.send(entry.clone());
         self.entries.push(entry);
 
         let audit_path: Vec<String> = self.entries.iter().map(|e| e.hash.clone()).collect();
         Ok(audit_path)
     }
 
+    /// Recomputes every entry's hash, checks the hash chain, and checks
+    /// that every signature matches its recorded public key. Entries logged
+    /// after a signing key was configured must be signed.
     pub fn verify_integrity(&self) -> bool {
-        for i in 1..self.entries.len() {
-            if self.entries[i].previous_hash != Some(self.entries[i - 1].hash.clone()) {
-                return false;
-            }
-        }
-        true
+        verify_chain(&self.entries, self.signed_from)
+    }
+
+    /// Checks integrity and that every entry was signed by one of `trusted`,
+    /// so a third party can confirm who produced the log.
+    pub fn verify_authenticity(&self, trusted: &[VerifyingKey]) -> bool {
+        self.verify_integrity()
+            && self.entries.iter().all(|e| {
+                verify_entry_signature(e).is_some_and(|signer| trusted.contains(&signer))
+            })
     }
 
     pub fn get_entries(&self) -> &[AuditEntry] {
@@ -72,6 +139,98 @@ impl AuditTrail {
     }
 }
 
+/// A live feed of audit entries that keeps count of entries it missed by
+/// lagging behind.
+pub struct AuditSubscription {
+    receiver: broadcast::Receiver<AuditEntry>,
+    dropped: u64,
+}
+
+impl AuditSubscription {
+    pub fn new(receiver: broadcast::Receiver<AuditEntry>) -> Self {
+        Self { receiver, dropped: 0 }
+    }
+
+    /// The next entry, skipping past any that were dropped. `None` once the
+    /// trail is gone.
+    pub async fn recv(&mut self) -> Option<AuditEntry> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(entry) => return Some(entry),
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!(missed, "Audit subscriber lagged, entries dropped");
+                    self.dropped += missed;
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Entries this subscriber has missed so far.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+/// Checks exported `entries`, e.g. from `GET /api/v1/audit`, the way
+/// [`AuditTrail::verify_integrity`] does. With `require_signatures`, every
+/// entry must be signed.
+pub fn verify_entries(entries: &[AuditEntry], require_signatures: bool) -> bool {
+    verify_chain(entries, require_signatures.then_some(0))
+}
+
+/// Entries from index `signed_from` on must carry a signature.
+fn verify_chain(entries: &[AuditEntry], signed_from: Option<usize>) -> bool {
+    let mut previous_hash = None;
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.previous_hash != previous_hash || entry.hash != entry_hash(entry) {
+            return false;
+        }
+        let must_be_signed = signed_from.is_some_and(|from| index >= from);
+        let claims_signature = entry.signature.is_some() || entry.signer_public_key.is_some();
+        if (must_be_signed || claims_signature) && verify_entry_signature(entry).is_none() {
+            return false;
+        }
+        previous_hash = Some(entry.hash.clone());
+    }
+    true
+}
+
+/// SHA-256 over every field except the hash itself and the signature of
+/// it. The fields are JSON-encoded so distinct entries never share an input.
+fn entry_hash(entry: &AuditEntry) -> String {
+    let fields = serde_json::json!([
+        entry.timestamp,
+        entry.query,
+        entry.node_path,
+        entry.cluster_path,
+        entry.previous_hash,
+        entry.signer_public_key,
+        entry.key_note,
+        entry.truncation,
+    ]);
+    let mut hasher = Sha256::new();
+    hasher.update(fields.to_string());
+    hex::encode(hasher.finalize())
+}
+
+/// Returns the signer's key if the entry carries a valid signature over its hash.
+fn verify_entry_signature(entry: &AuditEntry) -> Option<VerifyingKey> {
+    let key_bytes: [u8; 32] = hex::decode(entry.signer_public_key.as_ref()?)
+        .ok()?
+        .try_into()
+        .ok()?;
+    let signature_bytes: [u8; 64] = hex::decode(entry.signature.as_ref()?)
+        .ok()?
+        .try_into()
+        .ok()?;
+
+    let key = VerifyingKey::from_bytes(&key_bytes).ok()?;
+    key.verify(entry.hash.as_bytes(), &Signature::from_bytes(&signature_bytes))
+        .ok()?;
+    Some(key)
+}
+
 impl Default for AuditTrail {
     fn default() -> Self {
         Self::new()
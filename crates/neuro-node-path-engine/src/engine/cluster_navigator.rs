@@ -1,38 +1,172 @@
-use crate::{core::Cluster, engine::QueryContext};
-use dashmap::DashMap;
-use std::sync::Arc;
+use crate::{
+    core::{Cluster, Node},
+    engine::QueryContext,
+    i18n::{StopWords, Translator},
+    store::Table,
+};
+use std::collections::{HashMap, HashSet};
+
+const DAMPING: f64 = 0.85;
+const RANK_ITERATIONS: usize = 30;
 
 pub struct ClusterNavigator {
     visited_clusters: Vec<String>,
+    stop_words: StopWords,
 }
 
 impl ClusterNavigator {
     pub fn new() -> Self {
         Self {
             visited_clusters: Vec::new(),
+            stop_words: StopWords::default(),
         }
     }
 
+    /// Replaces the built-in stop words ignored when ranking summary
+    /// sentences.
+    pub fn with_stop_words(mut self, stop_words: StopWords) -> Self {
+        self.stop_words = stop_words;
+        self
+    }
+
     pub fn navigate(
         &self,
         context: &QueryContext,
-        clusters: &Arc<DashMap<String, Cluster>>,
+        clusters: &dyn Table<Cluster>,
     ) -> anyhow::Result<Vec<String>> {
         let mut cluster_path = Vec::new();
 
-        for cluster_ref in clusters.iter() {
-            let cluster = cluster_ref.value();
+        clusters.for_each(&mut |cluster| {
             if context.query.contains(&cluster.name) {
                 cluster_path.push(cluster.id.clone());
             }
-        }
+        })?;
 
+        cluster_path.sort();
         Ok(cluster_path)
     }
 
     pub fn mark_visited(&mut self, cluster_id: String) {
         self.visited_clusters.push(cluster_id);
     }
+
+    /// The most representative sentence from the documentation and
+    /// signatures of the cluster's members, chosen by TextRank. Falls back to
+    /// the cluster name and member count when no member has either.
+    pub fn summarize(&self, cluster: &Cluster, nodes: &HashMap<String, Node>) -> String {
+        let sentences = member_sentences(cluster, nodes);
+        match representative_sentence(&sentences, &self.stop_words) {
+            Some(sentence) => sentence.to_string(),
+            None => format!("{} with {} nodes", cluster.name, cluster.node_ids.len()),
+        }
+    }
+
+    /// [`Self::summarize`] in `language`, translated term by term from
+    /// English through `translator`. Words without a translation are kept.
+    pub fn summarize_in(
+        &self,
+        cluster: &Cluster,
+        nodes: &HashMap<String, Node>,
+        translator: &Translator,
+        language: &str,
+    ) -> String {
+        let sentences = member_sentences(cluster, nodes);
+        let Some(sentence) = representative_sentence(&sentences, &self.stop_words) else {
+            return match language {
+                "de" => format!("{} mit {} Knoten", cluster.name, cluster.node_ids.len()),
+                _ => self.summarize(cluster, nodes),
+            };
+        };
+        if language == "en" {
+            return sentence.to_string();
+        }
+        sentence
+            .split(' ')
+            .map(|word| {
+                translator
+                    .translate("en", language, &word.to_lowercase())
+                    .unwrap_or_else(|| word.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Sentences from member documentation followed by member signatures, in
+/// member id order so ranking ties resolve deterministically.
+fn member_sentences(cluster: &Cluster, nodes: &HashMap<String, Node>) -> Vec<String> {
+    let mut ids: Vec<&String> = cluster.node_ids.iter().collect();
+    ids.sort();
+
+    let mut sentences = Vec::new();
+    for node in ids.into_iter().filter_map(|id| nodes.get(id)) {
+        if let Some(doc) = &node.metadata.documentation {
+            sentences.extend(
+                doc.split_terminator(['.', '!', '?', '\n'])
+                    .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+                    .filter(|s| !s.is_empty()),
+            );
+        }
+        if let Some(signature) = &node.metadata.signature {
+            if !signature.trim().is_empty() {
+                sentences.push(signature.trim().to_string());
+            }
+        }
+    }
+    sentences
+}
+
+/// Ranks `sentences` by PageRank over a graph weighted by shared words,
+/// ignoring stop words, and returns the highest ranked one, the earliest on
+/// ties.
+fn representative_sentence<'a>(
+    sentences: &'a [String],
+    stop_words: &StopWords,
+) -> Option<&'a str> {
+    let words: Vec<HashSet<String>> = sentences
+        .iter()
+        .map(|s| {
+            s.split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty() && !stop_words.contains_any(w))
+                .map(str::to_lowercase)
+                .collect()
+        })
+        .collect();
+
+    let n = sentences.len();
+    let mut weights = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let shared = words[i].intersection(&words[j]).count();
+            let norm = (words[i].len() as f64).ln() + (words[j].len() as f64).ln();
+            if shared > 0 && norm > 0.0 {
+                weights[i][j] = shared as f64 / norm;
+                weights[j][i] = weights[i][j];
+            }
+        }
+    }
+    let out_weight: Vec<f64> = weights.iter().map(|row| row.iter().sum()).collect();
+
+    let mut scores = vec![1.0; n];
+    for _ in 0..RANK_ITERATIONS {
+        scores = (0..n)
+            .map(|i| {
+                let incoming: f64 = (0..n)
+                    .filter(|&j| out_weight[j] > 0.0)
+                    .map(|j| weights[j][i] / out_weight[j] * scores[j])
+                    .sum();
+                (1.0 - DAMPING) + DAMPING * incoming
+            })
+            .collect();
+    }
+
+    let mut best: Option<usize> = None;
+    for i in 0..n {
+        if best.is_none_or(|b| scores[i] > scores[b]) {
+            best = Some(i);
+        }
+    }
+    best.map(|i| sentences[i].as_str())
 }
 
 impl Default for ClusterNavigator {
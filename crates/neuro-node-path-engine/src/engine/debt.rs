@@ -0,0 +1,26 @@
+use crate::{engine::NeuroNodePathEngine, repository::DebtMarker};
+
+/// Key in `NodeMetadata::properties` holding a file's debt markers as a JSON
+/// array of [`DebtMarker`]s, filled in during indexing.
+pub const DEBT_MARKERS_PROPERTY: &str = "markers";
+
+impl NeuroNodePathEngine {
+    /// Every debt marker recorded while indexing, with the id of the node it
+    /// was found in, sorted by node id and line.
+    pub fn debt_markers(&self) -> anyhow::Result<Vec<(String, DebtMarker)>> {
+        let mut markers = Vec::new();
+        self.store.nodes().for_each(&mut |node| {
+            let Some(recorded) = node.metadata.properties.get(DEBT_MARKERS_PROPERTY) else {
+                return;
+            };
+            match serde_json::from_value::<Vec<DebtMarker>>(recorded.clone()) {
+                Ok(recorded) => {
+                    markers.extend(recorded.into_iter().map(|marker| (node.id.clone(), marker)))
+                }
+                Err(e) => tracing::warn!("Ignoring malformed debt markers on {}: {}", node.id, e),
+            }
+        })?;
+        markers.sort_by(|a, b| (&a.0, a.1.line).cmp(&(&b.0, b.1.line)));
+        Ok(markers)
+    }
+}
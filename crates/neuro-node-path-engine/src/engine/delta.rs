@@ -0,0 +1,126 @@
+use crate::{
+    core::{channel::SignalType, NeuralChannel, Node},
+    engine::NeuroNodePathEngine,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The nodes and channels of the graph at one point in time, from
+/// [`NeuroNodePathEngine::snapshot`]. Serializable, so it can be kept
+/// between runs and diffed after the next index.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    pub nodes: Vec<Node>,
+    pub channels: Vec<NeuralChannel>,
+}
+
+/// What changed since a [`GraphSnapshot`], from
+/// [`NeuroNodePathEngine::diff_against`]. Added and changed entries hold
+/// the current values, removed ones the snapshot's; each list is sorted
+/// by id.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GraphDelta {
+    pub added_nodes: Vec<Node>,
+    pub removed_nodes: Vec<Node>,
+    pub changed_nodes: Vec<Node>,
+    pub added_channels: Vec<NeuralChannel>,
+    pub removed_channels: Vec<NeuralChannel>,
+}
+
+impl GraphDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_channels.is_empty()
+            && self.removed_channels.is_empty()
+    }
+}
+
+/// Repository namespace, source path and name: what identifies a node
+/// across index runs.
+type NodeKey = (Option<String>, String, String);
+type ChannelKey = (NodeKey, NodeKey, SignalType);
+
+impl NeuroNodePathEngine {
+    /// Every node and channel, sorted by id.
+    pub fn snapshot(&self) -> anyhow::Result<GraphSnapshot> {
+        let mut nodes = self.store.nodes().values()?;
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut channels = self.store.channels().values()?;
+        channels.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(GraphSnapshot { nodes, channels })
+    }
+
+    /// Changes from `previous` to the current graph.
+    ///
+    /// Node ids are regenerated on every index, so nodes are matched by
+    /// repository namespace, source path and name instead, and channels by
+    /// the keys of their endpoints plus their signal type. A matched node
+    /// has changed when its type or metadata differ. Channels whose
+    /// endpoints are missing from their snapshot are ignored.
+    pub fn diff_against(&self, previous: &GraphSnapshot) -> anyhow::Result<GraphDelta> {
+        let current = self.snapshot()?;
+
+        let old_nodes: HashMap<NodeKey, &Node> =
+            previous.nodes.iter().map(|node| (node_key(node), node)).collect();
+        let new_nodes: HashMap<NodeKey, &Node> =
+            current.nodes.iter().map(|node| (node_key(node), node)).collect();
+
+        let mut delta = GraphDelta::default();
+        for node in &current.nodes {
+            match old_nodes.get(&node_key(node)) {
+                None => delta.added_nodes.push(node.clone()),
+                Some(old) if old.node_type != node.node_type || old.metadata != node.metadata => {
+                    delta.changed_nodes.push(node.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        delta.removed_nodes = previous
+            .nodes
+            .iter()
+            .filter(|node| !new_nodes.contains_key(&node_key(node)))
+            .cloned()
+            .collect();
+
+        let old_channels = keyed_channels(previous);
+        let new_channels = keyed_channels(&current);
+        delta.added_channels = added(&new_channels, &old_channels);
+        delta.removed_channels = added(&old_channels, &new_channels);
+        Ok(delta)
+    }
+}
+
+fn node_key(node: &Node) -> NodeKey {
+    (node.repo_id.clone(), node.source_path.clone(), node.name.clone())
+}
+
+fn keyed_channels(snapshot: &GraphSnapshot) -> Vec<(ChannelKey, &NeuralChannel)> {
+    let keys: HashMap<&str, NodeKey> = snapshot
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node_key(node)))
+        .collect();
+    snapshot
+        .channels
+        .iter()
+        .filter_map(|channel| {
+            let from = keys.get(channel.from_node_id.as_str())?.clone();
+            let to = keys.get(channel.to_node_id.as_str())?.clone();
+            Some(((from, to, channel.signal_type.clone()), channel))
+        })
+        .collect()
+}
+
+/// Channels of `side` whose key does not occur in `other`, in `side` order.
+fn added(
+    side: &[(ChannelKey, &NeuralChannel)],
+    other: &[(ChannelKey, &NeuralChannel)],
+) -> Vec<NeuralChannel> {
+    let other_keys: HashSet<&ChannelKey> = other.iter().map(|(key, _)| key).collect();
+    side.iter()
+        .filter(|(key, _)| !other_keys.contains(key))
+        .map(|(_, channel)| (*channel).clone())
+        .collect()
+}
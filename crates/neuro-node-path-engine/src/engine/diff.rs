@@ -0,0 +1,108 @@
+use crate::engine::QueryResult;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+
+/// A field whose value differs between two results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
+
+/// What changed between two [`QueryResult`]s, from [`diff_results`].
+/// `Display` renders a line per change for humans; serialize it for tools.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct QueryResultDiff {
+    /// Node ids in the new path only, in new-path order.
+    pub nodes_inserted: Vec<String>,
+    /// Node ids in the old path only, in old-path order.
+    pub nodes_removed: Vec<String>,
+    /// Both paths hold the same ids in a different order.
+    pub nodes_reordered: bool,
+    pub clusters_inserted: Vec<String>,
+    pub clusters_removed: Vec<String>,
+    pub validation_status: Option<Change<String>>,
+    pub explanation_en: Option<Change<String>>,
+    pub explanation_de: Option<Change<String>>,
+    pub partial: Option<Change<bool>>,
+}
+
+impl QueryResultDiff {
+    /// No difference beyond the fields [`diff_results`] ignores.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Compares two results of the same query, ignoring `request_id`,
+/// `timestamp` and the audit chain, which differ on every run.
+pub fn diff_results(old: &QueryResult, new: &QueryResult) -> QueryResultDiff {
+    let (nodes_inserted, nodes_removed) = list_changes(&old.node_path, &new.node_path);
+    let (clusters_inserted, clusters_removed) = list_changes(&old.cluster_path, &new.cluster_path);
+    QueryResultDiff {
+        nodes_reordered: nodes_inserted.is_empty()
+            && nodes_removed.is_empty()
+            && old.node_path != new.node_path,
+        nodes_inserted,
+        nodes_removed,
+        clusters_inserted,
+        clusters_removed,
+        validation_status: change(&old.validation_status, &new.validation_status),
+        explanation_en: change(&old.explanation_en, &new.explanation_en),
+        explanation_de: change(&old.explanation_de, &new.explanation_de),
+        partial: change(&old.partial, &new.partial),
+    }
+}
+
+fn list_changes(old: &[String], new: &[String]) -> (Vec<String>, Vec<String>) {
+    let old_ids: HashSet<&String> = old.iter().collect();
+    let new_ids: HashSet<&String> = new.iter().collect();
+    let inserted = new.iter().filter(|id| !old_ids.contains(id)).cloned().collect();
+    let removed = old.iter().filter(|id| !new_ids.contains(id)).cloned().collect();
+    (inserted, removed)
+}
+
+fn change<T: Clone + PartialEq>(old: &T, new: &T) -> Option<Change<T>> {
+    (old != new).then(|| Change {
+        old: old.clone(),
+        new: new.clone(),
+    })
+}
+
+impl fmt::Display for QueryResultDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for id in &self.nodes_inserted {
+            writeln!(f, "+ node {}", id)?;
+        }
+        for id in &self.nodes_removed {
+            writeln!(f, "- node {}", id)?;
+        }
+        if self.nodes_reordered {
+            writeln!(f, "~ node path reordered")?;
+        }
+        for id in &self.clusters_inserted {
+            writeln!(f, "+ cluster {}", id)?;
+        }
+        for id in &self.clusters_removed {
+            writeln!(f, "- cluster {}", id)?;
+        }
+        let texts = [
+            ("validation", &self.validation_status),
+            ("explanation (en)", &self.explanation_en),
+            ("explanation (de)", &self.explanation_de),
+        ];
+        for (label, text) in texts {
+            if let Some(change) = text {
+                writeln!(f, "~ {}: {:?} -> {:?}", label, change.old, change.new)?;
+            }
+        }
+        if let Some(change) = &self.partial {
+            writeln!(f, "~ partial: {} -> {}", change.old, change.new)?;
+        }
+        Ok(())
+    }
+}
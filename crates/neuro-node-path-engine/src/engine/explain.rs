@@ -0,0 +1,118 @@
+use crate::engine::{NeuroNodePathEngine, PathResolver, QueryResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Why a node was added to a resolved path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StepReason {
+    /// The node starts the path: its name matches the query text the way
+    /// path resolution matches it, or it passed every filter of a
+    /// structured query without free text.
+    MatchedQuery { term: String },
+    /// The node was reached from its parent, which is earlier on the path.
+    FollowedParent { parent_id: String },
+    /// The node no longer exists or its inclusion cannot be reconstructed.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathStep {
+    pub node_id: String,
+    pub node_name: String,
+    pub reason: StepReason,
+    pub reason_en: String,
+    pub reason_de: String,
+    pub channel_used: Option<String>,
+    pub cluster: Option<String>,
+}
+
+impl NeuroNodePathEngine {
+    /// Reconstructs a per-hop justification for `result.node_path` against
+    /// the current graph, with English and German wording for each step.
+    pub fn explain_path(&self, result: &QueryResult) -> anyhow::Result<Vec<PathStep>> {
+        let resolver = PathResolver::new().with_normalized_names(self.config.normalize_names);
+        let matches_query = |node: &crate::core::Node| match &result.structured {
+            Some(structured) if structured.free_text.is_empty() => structured.matches(node),
+            Some(structured) => resolver.matches_query(&structured.free_text, node),
+            None => resolver.matches_query(&result.query, node),
+        };
+        let mut earlier: HashSet<&str> = HashSet::new();
+        let mut steps = Vec::with_capacity(result.node_path.len());
+
+        for node_id in &result.node_path {
+            let Some(node) = self.get_node(node_id)? else {
+                steps.push(PathStep {
+                    node_id: node_id.clone(),
+                    node_name: String::new(),
+                    reason: StepReason::Unknown,
+                    reason_en: "Node is no longer part of the graph".to_string(),
+                    reason_de: "Knoten ist nicht mehr Teil des Graphen".to_string(),
+                    channel_used: None,
+                    cluster: None,
+                });
+                earlier.insert(node_id);
+                continue;
+            };
+
+            let parent_on_path = node
+                .parent_id
+                .as_deref()
+                .filter(|parent| earlier.contains(parent));
+
+            let (reason, reason_en, reason_de) = if matches_query(&node) {
+                (
+                    StepReason::MatchedQuery {
+                        term: node.name.clone(),
+                    },
+                    format!("Name '{}' matched the query", node.name),
+                    format!("Name '{}' entspricht der Anfrage", node.name),
+                )
+            } else if let Some(parent_id) = parent_on_path {
+                (
+                    StepReason::FollowedParent {
+                        parent_id: parent_id.to_string(),
+                    },
+                    format!("Reached as a child of node {}", parent_id),
+                    format!("Als Kindknoten von Knoten {} erreicht", parent_id),
+                )
+            } else {
+                (
+                    StepReason::Unknown,
+                    "Inclusion could not be reconstructed".to_string(),
+                    "Aufnahme konnte nicht rekonstruiert werden".to_string(),
+                )
+            };
+
+            let channel_used = self
+                .store
+                .channels()
+                .filter(&mut |c| {
+                    c.to_node_id == node.id && earlier.contains(c.from_node_id.as_str())
+                })?
+                .into_iter()
+                .map(|c| c.id)
+                .min();
+
+            let cluster = self
+                .store
+                .clusters()
+                .filter(&mut |c| c.node_ids.contains(&node.id))?
+                .into_iter()
+                .max_by_key(|c| result.cluster_path.contains(&c.id))
+                .map(|c| c.name);
+
+            steps.push(PathStep {
+                node_id: node.id.clone(),
+                node_name: node.name.clone(),
+                reason,
+                reason_en,
+                reason_de,
+                channel_used,
+                cluster,
+            });
+            earlier.insert(node_id);
+        }
+
+        Ok(steps)
+    }
+}
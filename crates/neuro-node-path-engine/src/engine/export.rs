@@ -0,0 +1,150 @@
+use crate::{
+    core::{NeuralChannel, Node},
+    engine::{GraphSnapshot, NeuroNodePathEngine},
+    i18n::export::escape_xml,
+};
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// Serialization formats for [`NeuroNodePathEngine::write_graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz `digraph`.
+    Dot,
+    GraphMl,
+    /// `{"nodes": [...], "channels": [...]}` with the serde form of each.
+    Json,
+}
+
+impl GraphFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "text/vnd.graphviz",
+            GraphFormat::GraphMl => "application/graphml+xml",
+            GraphFormat::Json => "application/json",
+        }
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "dot" => Ok(GraphFormat::Dot),
+            "graphml" => Ok(GraphFormat::GraphMl),
+            "json" => Ok(GraphFormat::Json),
+            other => anyhow::bail!("Unknown graph format '{}'; expected dot, graphml or json", other),
+        }
+    }
+}
+
+impl NeuroNodePathEngine {
+    /// Writes every node and channel to `out` in `format`, sorted by id.
+    /// Output is written element by element, so wrapping `out` in a
+    /// `BufWriter` over a socket streams the graph without rendering it
+    /// into memory first. A store failure is reported as an I/O error.
+    pub fn write_graph(&self, format: GraphFormat, out: &mut dyn Write) -> io::Result<()> {
+        self.snapshot()
+            .map_err(io::Error::other)?
+            .write_graph(format, out)
+    }
+
+    /// [`Self::write_graph`] into a string.
+    pub fn export_graph(&self, format: GraphFormat) -> anyhow::Result<String> {
+        let mut out = Vec::new();
+        self.snapshot()?.write_graph(format, &mut out)?;
+        Ok(String::from_utf8(out).expect("graph exports are UTF-8"))
+    }
+}
+
+impl GraphSnapshot {
+    /// Writes the snapshot's nodes and channels to `out` in `format`, like
+    /// [`NeuroNodePathEngine::write_graph`]. Lets a caller release its lock
+    /// on the engine before a slow writer drains the output.
+    pub fn write_graph(&self, format: GraphFormat, out: &mut dyn Write) -> io::Result<()> {
+        match format {
+            GraphFormat::Dot => write_dot(&self.nodes, &self.channels, out),
+            GraphFormat::GraphMl => write_graphml(&self.nodes, &self.channels, out),
+            GraphFormat::Json => write_json(&self.nodes, &self.channels, out),
+        }?;
+        out.flush()
+    }
+}
+
+fn write_dot(nodes: &[Node], channels: &[NeuralChannel], out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "digraph neuro_node_path {{")?;
+    for node in nodes {
+        writeln!(
+            out,
+            "  \"{}\" [label=\"{}\", type=\"{}\"];",
+            escape_dot(&node.id),
+            escape_dot(&node.name),
+            node.node_type
+        )?;
+    }
+    for channel in channels {
+        writeln!(
+            out,
+            "  \"{}\" -> \"{}\" [id=\"{}\", signal=\"{}\", weight={}];",
+            escape_dot(&channel.from_node_id),
+            escape_dot(&channel.to_node_id),
+            escape_dot(&channel.id),
+            channel.signal_type,
+            channel.weight
+        )?;
+    }
+    writeln!(out, "}}")
+}
+
+fn write_graphml(nodes: &[Node], channels: &[NeuralChannel], out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(out, "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">")?;
+    writeln!(out, "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>")?;
+    writeln!(out, "  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>")?;
+    writeln!(out, "  <key id=\"signal\" for=\"edge\" attr.name=\"signal\" attr.type=\"string\"/>")?;
+    writeln!(out, "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>")?;
+    writeln!(out, "  <graph id=\"neuro_node_path\" edgedefault=\"directed\">")?;
+    for node in nodes {
+        writeln!(out, "    <node id=\"{}\">", escape_xml(&node.id))?;
+        writeln!(out, "      <data key=\"name\">{}</data>", escape_xml(&node.name))?;
+        writeln!(out, "      <data key=\"type\">{}</data>", node.node_type)?;
+        writeln!(out, "    </node>")?;
+    }
+    for channel in channels {
+        writeln!(
+            out,
+            "    <edge id=\"{}\" source=\"{}\" target=\"{}\">",
+            escape_xml(&channel.id),
+            escape_xml(&channel.from_node_id),
+            escape_xml(&channel.to_node_id)
+        )?;
+        writeln!(out, "      <data key=\"signal\">{}</data>", channel.signal_type)?;
+        writeln!(out, "      <data key=\"weight\">{}</data>", channel.weight)?;
+        writeln!(out, "    </edge>")?;
+    }
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")
+}
+
+fn write_json(nodes: &[Node], channels: &[NeuralChannel], out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "{{\"nodes\":[")?;
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+        serde_json::to_writer(&mut *out, node)?;
+    }
+    write!(out, "],\"channels\":[")?;
+    for (i, channel) in channels.iter().enumerate() {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+        serde_json::to_writer(&mut *out, channel)?;
+    }
+    write!(out, "]}}")
+}
+
+fn escape_dot(input: &str) -> String {
+    input.replace('\\', "\\\\").replace('"', "\\\"")
+}
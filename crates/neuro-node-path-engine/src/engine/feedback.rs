@@ -0,0 +1,145 @@
+use crate::{
+    core::{channel::SignalType, NeuralChannel},
+    engine::NeuroNodePathEngine,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+impl NeuroNodePathEngine {
+    /// Recurrent connections: cycles formed by active signal-carrying
+    /// channels (every type except `DependencyLink`), where a node's output
+    /// eventually feeds back into its input.
+    ///
+    /// One cycle is returned per strongly connected group of nodes: the
+    /// shortest loop through the group's smallest node id, listed from that
+    /// node onwards without repeating it. Cycles are sorted by first node.
+    pub fn feedback_loops(&self) -> anyhow::Result<Vec<Vec<String>>> {
+        self.cycles_over(|c| c.signal_type != SignalType::DependencyLink)
+    }
+
+    /// Cycles made only of `DependencyLink` channels, in the same form as
+    /// [`Self::feedback_loops`]. These are usually accidental architecture
+    /// cycles rather than intended recurrence. Cycles mixing both kinds of
+    /// channels are reported by neither.
+    pub fn dependency_cycles(&self) -> anyhow::Result<Vec<Vec<String>>> {
+        self.cycles_over(|c| c.signal_type == SignalType::DependencyLink)
+    }
+
+    fn cycles_over(
+        &self,
+        include: impl Fn(&NeuralChannel) -> bool,
+    ) -> anyhow::Result<Vec<Vec<String>>> {
+        let mut forward: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut reverse: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let channels = self.store.channels().filter(&mut |c| c.active && include(c))?;
+        for channel in channels {
+            if !self.store.nodes().contains(&channel.from_node_id)?
+                || !self.store.nodes().contains(&channel.to_node_id)?
+            {
+                continue;
+            }
+            let (from, to) = (channel.from_node_id.clone(), channel.to_node_id.clone());
+            forward.entry(from.clone()).or_default().insert(to.clone());
+            reverse.entry(to).or_default().insert(from);
+        }
+
+        let mut cycles: Vec<Vec<String>> = strongly_connected(&forward, &reverse)
+            .into_iter()
+            .filter_map(|component| shortest_cycle(&forward, &component))
+            .collect();
+        cycles.sort();
+        Ok(cycles)
+    }
+}
+
+/// Kosaraju's algorithm with explicit stacks, so deep graphs cannot overflow.
+fn strongly_connected(
+    forward: &BTreeMap<String, BTreeSet<String>>,
+    reverse: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<BTreeSet<String>> {
+    let vertices: BTreeSet<&str> = forward
+        .keys()
+        .chain(reverse.keys())
+        .map(String::as_str)
+        .collect();
+
+    let mut visited: BTreeSet<&str> = BTreeSet::new();
+    let mut finished: Vec<&str> = Vec::new();
+    for &start in &vertices {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut stack = vec![(start, successors(forward, start))];
+        while let Some((vertex, next)) = stack.last_mut() {
+            match next.pop() {
+                Some(n) if visited.insert(n) => {
+                    let children = successors(forward, n);
+                    stack.push((n, children));
+                }
+                Some(_) => {}
+                None => {
+                    finished.push(vertex);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    let mut assigned: BTreeSet<&str> = BTreeSet::new();
+    let mut components = Vec::new();
+    for &start in finished.iter().rev() {
+        if !assigned.insert(start) {
+            continue;
+        }
+        let mut component = BTreeSet::from([start.to_string()]);
+        let mut stack = vec![start];
+        while let Some(vertex) = stack.pop() {
+            for previous in successors(reverse, vertex) {
+                if assigned.insert(previous) {
+                    component.insert(previous.to_string());
+                    stack.push(previous);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+fn successors<'a>(graph: &'a BTreeMap<String, BTreeSet<String>>, vertex: &str) -> Vec<&'a str> {
+    graph
+        .get(vertex)
+        .map(|next| next.iter().rev().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Breadth-first search for the shortest loop from the component's smallest
+/// node back to itself, staying inside the component. Single nodes only form
+/// a cycle through a self-loop.
+fn shortest_cycle(
+    forward: &BTreeMap<String, BTreeSet<String>>,
+    component: &BTreeSet<String>,
+) -> Option<Vec<String>> {
+    let start = component.first()?;
+    let mut came_from: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([start.as_str()]);
+
+    while let Some(vertex) = queue.pop_front() {
+        for next in forward.get(vertex).into_iter().flatten() {
+            if next == start {
+                let mut cycle = vec![vertex.to_string()];
+                let mut current = vertex;
+                while current != start {
+                    current = came_from[current];
+                    cycle.push(current.to_string());
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if component.contains(next) && !came_from.contains_key(next.as_str()) {
+                came_from.insert(next, vertex);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,96 @@
+use crate::engine::NeuroNodePathEngine;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+const SOURCE: usize = 0;
+const SINK: usize = 1;
+
+impl NeuroNodePathEngine {
+    /// Maximum flow from `cluster_a` to `cluster_b` over active channels,
+    /// with each channel's `bandwidth` as its capacity. Every member of a
+    /// cluster is contracted into one supernode first; a node in both
+    /// clusters counts as part of `cluster_a`. Returns 0.0 when either
+    /// cluster is unknown or no channel path links them.
+    pub fn cluster_max_flow(&self, cluster_a: &str, cluster_b: &str) -> anyhow::Result<f64> {
+        let (Some(a), Some(b)) = (self.get_cluster(cluster_a)?, self.get_cluster(cluster_b)?) else {
+            return Ok(0.0);
+        };
+        if a.id == b.id {
+            return Ok(0.0);
+        }
+
+        let mut vertex: HashMap<String, usize> = HashMap::new();
+        for id in &a.node_ids {
+            vertex.insert(id.clone(), SOURCE);
+        }
+        for id in b.node_ids.iter().filter(|id| !a.node_ids.contains(*id)) {
+            vertex.insert(id.clone(), SINK);
+        }
+
+        let mut capacity: Vec<HashMap<usize, f64>> = vec![HashMap::new(), HashMap::new()];
+        let mut vertex_of = |id: &str, capacity: &mut Vec<HashMap<usize, f64>>| {
+            *vertex.entry(id.to_string()).or_insert_with(|| {
+                capacity.push(HashMap::new());
+                capacity.len() - 1
+            })
+        };
+        for channel in self.store.channels().values()? {
+            if !channel.active || channel.bandwidth <= 0.0 {
+                continue;
+            }
+            let from = vertex_of(&channel.from_node_id, &mut capacity);
+            let to = vertex_of(&channel.to_node_id, &mut capacity);
+            if from == to {
+                continue;
+            }
+            *capacity[from].entry(to).or_insert(0.0) += channel.bandwidth;
+            capacity[to].entry(from).or_insert(0.0);
+        }
+
+        Ok(edmonds_karp(&mut capacity))
+    }
+}
+
+/// Pushes flow from `SOURCE` to `SINK` along shortest augmenting paths,
+/// consuming `capacity` into the residual graph. Returns the total flow.
+fn edmonds_karp(capacity: &mut [HashMap<usize, f64>]) -> f64 {
+    let mut total = 0.0;
+    loop {
+        let mut parent: Vec<Option<usize>> = vec![None; capacity.len()];
+        let mut seen: HashSet<usize> = HashSet::from([SOURCE]);
+        let mut queue = VecDeque::from([SOURCE]);
+        while let Some(current) = queue.pop_front() {
+            if current == SINK {
+                break;
+            }
+            let mut next: Vec<usize> = capacity[current]
+                .iter()
+                .filter(|(_, residual)| **residual > 0.0)
+                .map(|(to, _)| *to)
+                .collect();
+            next.sort_unstable();
+            for to in next {
+                if seen.insert(to) {
+                    parent[to] = Some(current);
+                    queue.push_back(to);
+                }
+            }
+        }
+        if !seen.contains(&SINK) {
+            return total;
+        }
+
+        let mut bottleneck = f64::INFINITY;
+        let mut to = SINK;
+        while let Some(from) = parent[to] {
+            bottleneck = bottleneck.min(capacity[from][&to]);
+            to = from;
+        }
+        let mut to = SINK;
+        while let Some(from) = parent[to] {
+            *capacity[from].get_mut(&to).unwrap() -= bottleneck;
+            *capacity[to].entry(from).or_insert(0.0) += bottleneck;
+            to = from;
+        }
+        total += bottleneck;
+    }
+}
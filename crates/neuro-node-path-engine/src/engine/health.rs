@@ -0,0 +1,76 @@
+use crate::engine::{LayerRule, NeuroNodePathEngine};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Share of [`HealthScore::value`] each component contributes.
+const WEIGHTS: &[(&str, f64)] = &[
+    ("cohesion", 0.3),
+    ("orphans", 0.2),
+    ("cycles", 0.3),
+    ("layering", 0.2),
+];
+
+/// One number for the shape of the graph, from
+/// [`NeuroNodePathEngine::health_score`]. Higher is healthier.
+///
+/// `value` is the weighted mean of four components, each scored 0..100:
+///
+/// | component  | weight | score                                               |
+/// |------------|--------|-----------------------------------------------------|
+/// | `cohesion` | 30%    | mean cluster cohesion × 100; 100 without clusters   |
+/// | `orphans`  | 20%    | share of nodes that are not orphans × 100           |
+/// | `cycles`   | 30%    | 100 / (1 + dependency cycles)                       |
+/// | `layering` | 20%    | 100 / (1 + layer rule violations)                   |
+///
+/// An empty graph scores 100.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthScore {
+    pub value: f64,
+    pub components: HashMap<String, f64>,
+}
+
+impl NeuroNodePathEngine {
+    /// [`HealthScore`] without layer rules, so `layering` is always 100.
+    pub fn health_score(&self) -> anyhow::Result<HealthScore> {
+        self.health_score_with(&[])
+    }
+
+    /// [`HealthScore`] with `layering` counting violations of `rules`
+    /// against the inferred layers.
+    pub fn health_score_with(&self, rules: &[LayerRule]) -> anyhow::Result<HealthScore> {
+        let stats = self.repository_stats()?;
+        let cohesion = if stats.cluster_count == 0 {
+            100.0
+        } else {
+            (stats.average_cohesion * 100.0).clamp(0.0, 100.0)
+        };
+        let orphans = if stats.node_count == 0 {
+            100.0
+        } else {
+            100.0 * (1.0 - stats.orphan_count as f64 / stats.node_count as f64)
+        };
+        let cycles = 100.0 / (1.0 + self.dependency_cycles()?.len() as f64);
+        let violations = if rules.is_empty() {
+            0
+        } else {
+            self.check_layer_rules(rules)?.len()
+        };
+        let layering = 100.0 / (1.0 + violations as f64);
+
+        let components: HashMap<String, f64> = [
+            ("cohesion", cohesion),
+            ("orphans", orphans),
+            ("cycles", cycles),
+            ("layering", layering),
+        ]
+        .into_iter()
+        .map(|(name, score)| (name.to_string(), score))
+        .collect();
+        let value = WEIGHTS
+            .iter()
+            .map(|(name, weight)| components[*name] * weight)
+            .sum();
+
+        Ok(HealthScore { value, components })
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{
+    core::channel::SignalType,
+    engine::{modules::module_parents, read_source, IndexReport, NeuroNodePathEngine},
+    repository::{CodeAnalyzer, CodeRepository},
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path};
+use tokio_util::sync::CancellationToken;
+
+impl NeuroNodePathEngine {
+    /// Re-indexes only `files`, given relative to the repository root, in
+    /// the `repo_id` namespace, e.g. the paths a push touched. Files that
+    /// still exist are read again and keep their node ids, new files get
+    /// nodes in their directory's cluster, and deleted files are removed
+    /// with their channels. Files that may declare a new file with `mod`
+    /// are read again too, so its dependency links are found.
+    ///
+    /// A namespace with no nodes yet is indexed in full instead. Limits
+    /// are enforced as for a full run; exceeding `max_channels` discards
+    /// the namespace.
+    ///
+    /// Absolute paths and paths with `..` are rejected before anything
+    /// changes. Files deeper than the repository's
+    /// [`CodeRepository::with_max_depth`] limit are counted in
+    /// `files_skipped` and left alone.
+    pub async fn reindex_files(
+        &mut self,
+        repo_id: &str,
+        repository: &CodeRepository,
+        files: &[String],
+    ) -> anyhow::Result<IndexReport> {
+        let namespace = Some(repo_id);
+        let mut report = IndexReport::default();
+        let mut relative_files = Vec::new();
+        for file in files {
+            let relative = Path::new(file);
+            let inside = relative
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+            if !inside {
+                anyhow::bail!("Changed file is outside the repository: {}", file);
+            }
+            let depth = relative
+                .components()
+                .filter(|c| matches!(c, Component::Normal(_)))
+                .count();
+            if repository.get_max_depth().is_some_and(|max| depth > max) {
+                report.files_skipped += 1;
+                continue;
+            }
+            relative_files.push(relative);
+        }
+
+        if self.namespace_len(namespace)? == 0 {
+            return self.index_repository_as(repo_id, repository).await;
+        }
+
+        let mut node_ids: HashMap<String, String> = HashMap::new();
+        self.store.nodes().for_each(&mut |n| {
+            if n.repo_id.as_deref() == namespace {
+                node_ids.insert(n.name.clone(), n.id.clone());
+            }
+        })?;
+
+        let root = repository.get_root_path();
+        let mut paths: BTreeSet<String> = relative_files
+            .iter()
+            .map(|file| root.join(file).to_string_lossy().into_owned())
+            .collect();
+        let added: Vec<String> = paths
+            .iter()
+            .filter(|path| !node_ids.contains_key(*path) && Path::new(path).is_file())
+            .cloned()
+            .collect();
+        for path in &added {
+            paths.extend(
+                module_parents(Path::new(path))
+                    .into_iter()
+                    .filter_map(|parent| parent.to_str().map(str::to_string))
+                    .filter(|parent| node_ids.contains_key(parent)),
+            );
+        }
+        self.check_graph_limits(
+            self.store.nodes().len()? + added.len(),
+            self.store.channels().len()?,
+        )?;
+        let analyzer = CodeAnalyzer::try_new()?;
+
+        let mut new_by_directory: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut module_declarations = Vec::new();
+        for path in paths {
+            let previous = node_ids.get(&path).cloned();
+            if let Some(id) = &previous {
+                self.store.channels().retain(&mut |c| {
+                    c.from_node_id != *id || c.signal_type != SignalType::DependencyLink
+                })?;
+                self.store
+                    .interfaces()
+                    .retain(&mut |i| i.source_node_id.as_deref() != Some(id.as_str()))?;
+            }
+            if !Path::new(&path).is_file() {
+                if let Some(id) = previous {
+                    self.remove_file_node(&id)?;
+                    node_ids.remove(&path);
+                }
+                continue;
+            }
+
+            let content = read_source(&path, &mut report).await;
+            let mut node = self.file_node(&path, namespace, content.as_deref());
+            node.id = previous.clone().unwrap_or_else(|| self.next_id());
+            if previous.is_none() {
+                let directory = Path::new(&path)
+                    .parent()
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                new_by_directory.entry(directory).or_default().push(node.id.clone());
+            }
+            let node_id = node.id.clone();
+            for observer in &self.observers {
+                observer.on_node_indexed(&node);
+            }
+            self.store.nodes().insert(node_id.clone(), node)?;
+            if let Some(code) = content.as_deref().filter(|_| path.ends_with(".rs")) {
+                self.discover_interfaces(&analyzer, &node_id, &path, code)?;
+                let modules = analyzer.module_declarations(code);
+                if !modules.is_empty() {
+                    module_declarations.push((node_id.clone(), path.clone(), modules));
+                }
+            }
+            node_ids.insert(path, node_id);
+            report.files_indexed += 1;
+        }
+        *self.name_index.write() = None;
+        // The stored fingerprint no longer describes the namespace.
+        self.last_index.remove(&namespace.map(str::to_string));
+
+        let channels = self.module_channels(&module_declarations, &node_ids);
+        let limits = self.check_graph_limits(
+            self.store.nodes().len()?,
+            self.store.channels().len()? + channels.len(),
+        );
+        if let Err(e) = limits {
+            self.clear_namespace(namespace)?;
+            return Err(e.into());
+        }
+        for channel in channels {
+            self.add_channel(channel)?;
+        }
+
+        if self.config.enable_clustering {
+            self.cluster_new_files(repo_id, new_by_directory)?;
+        }
+        Ok(report)
+    }
+
+    /// Removes a file node with the channels touching it, dropping it from
+    /// its clusters and removing clusters it leaves empty.
+    fn remove_file_node(&self, node_id: &str) -> anyhow::Result<()> {
+        self.store.nodes().remove(node_id)?;
+        self.store
+            .channels()
+            .retain(&mut |c| c.from_node_id != node_id && c.to_node_id != node_id)?;
+        for cluster in self.store.clusters().filter(&mut |c| c.node_ids.contains(node_id))? {
+            self.store.clusters().update(&cluster.id, &mut |c| c.remove_node(node_id))?;
+        }
+        self.store.clusters().retain(&mut |c| !c.node_ids.is_empty())?;
+        Ok(())
+    }
+
+    /// Adds new file nodes to the cluster of their directory in `repo_id`,
+    /// or forms one for a directory that has none.
+    fn cluster_new_files(
+        &self,
+        repo_id: &str,
+        by_directory: BTreeMap<String, Vec<String>>,
+    ) -> anyhow::Result<()> {
+        let nodes: HashMap<String, _> = self
+            .store
+            .nodes()
+            .values()?
+            .into_iter()
+            .map(|node| (node.id.clone(), node))
+            .collect();
+        let directory_of = |node_id: &String| {
+            nodes
+                .get(node_id)
+                .and_then(|node| Path::new(&node.name).parent())
+                .map(|p| p.to_string_lossy().into_owned())
+        };
+
+        let mut unclustered = BTreeMap::new();
+        for (directory, node_ids) in by_directory {
+            let new_ids: HashSet<&String> = node_ids.iter().collect();
+            let existing = self.store.clusters().filter(&mut |c| {
+                c.repo_id.as_deref() == Some(repo_id)
+                    && c.node_ids.iter().any(|id| {
+                        !new_ids.contains(id) && directory_of(id).as_deref() == Some(&directory)
+                    })
+            })?;
+            match existing.first() {
+                Some(cluster) => {
+                    self.store.clusters().update(&cluster.id, &mut |c| {
+                        for node_id in &node_ids {
+                            c.add_node(node_id.clone());
+                        }
+                        c.compute_cohesion(&nodes);
+                    })?;
+                }
+                None => {
+                    unclustered.insert(directory, node_ids);
+                }
+            }
+        }
+        self.cluster_by_directory(Some(repo_id), unclustered, &|_| {}, &CancellationToken::new())
+    }
+}
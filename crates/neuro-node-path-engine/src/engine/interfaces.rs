@@ -0,0 +1,59 @@
+use crate::{
+    core::{
+        interface::{InterfaceType, MethodSignature},
+        Interface,
+    },
+    engine::NeuroNodePathEngine,
+    repository::CodeAnalyzer,
+};
+use std::path::Path;
+
+impl NeuroNodePathEngine {
+    /// Registers one interface for the public functions in `code`, the Rust
+    /// source at `path`, and one per public trait it declares, all attributed
+    /// to `node_id`. Returns the number of interfaces registered.
+    pub(crate) fn discover_interfaces(
+        &self,
+        analyzer: &CodeAnalyzer,
+        node_id: &str,
+        path: &str,
+        code: &str,
+    ) -> anyhow::Result<usize> {
+        let module_name = Path::new(path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut discovered = vec![(module_name, analyzer.public_functions(code))];
+        discovered.extend(analyzer.public_traits(code));
+
+        let mut registered = 0;
+        for (name, methods) in discovered {
+            if methods.is_empty() {
+                continue;
+            }
+            let mut interface = discovered_interface(name, methods, node_id);
+            interface.id = self.next_id();
+            self.register_interface(interface)?;
+            registered += 1;
+        }
+        Ok(registered)
+    }
+
+    /// Interfaces discovered in the source of `node_id`, sorted by name.
+    pub fn interfaces_for_node(&self, node_id: &str) -> anyhow::Result<Vec<Interface>> {
+        let mut interfaces = self
+            .store
+            .interfaces()
+            .filter(&mut |i| i.source_node_id.as_deref() == Some(node_id))?;
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(interfaces)
+    }
+}
+
+fn discovered_interface(name: String, methods: Vec<MethodSignature>, node_id: &str) -> Interface {
+    let interface_type = InterfaceType::from_name(&name);
+    let mut interface = Interface::new(name, interface_type);
+    interface.exposed_methods = methods;
+    interface.source_node_id = Some(node_id.to_string());
+    interface
+}
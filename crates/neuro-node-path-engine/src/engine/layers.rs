@@ -0,0 +1,243 @@
+use crate::{
+    core::{cluster::ClusterType, Cluster},
+    engine::NeuroNodePathEngine,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Layer {
+    pub index: usize,
+    pub name: String,
+    pub node_ids: Vec<String>,
+}
+
+/// Result of [`NeuroNodePathEngine::infer_layers`]. `broken_channels` lists
+/// the channels ignored to make the graph acyclic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerInference {
+    pub layers: Vec<Layer>,
+    pub broken_channels: Vec<String>,
+}
+
+/// Forbids any channel from a node in `from_layer` to a node in `to_layer`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerRule {
+    pub from_layer: String,
+    pub to_layer: String,
+}
+
+impl LayerRule {
+    pub fn forbid(from_layer: &str, to_layer: &str) -> Self {
+        Self {
+            from_layer: from_layer.to_string(),
+            to_layer: to_layer.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Violation {
+    pub rule: LayerRule,
+    pub channel_id: String,
+    pub from_node_id: String,
+    pub to_node_id: String,
+}
+
+impl NeuroNodePathEngine {
+    /// Longest-path layering of the node graph along channel direction.
+    /// Nodes without incoming channels form layer 0, and every other node
+    /// sits one layer below its deepest predecessor. Cycles are broken by
+    /// dropping DFS back edges, visiting nodes and channels in id order so the
+    /// result is deterministic.
+    pub fn infer_layers(&self) -> anyhow::Result<LayerInference> {
+        let mut node_ids: Vec<String> =
+            self.store.nodes().values()?.into_iter().map(|n| n.id).collect();
+        node_ids.sort();
+        let known: HashSet<&str> = node_ids.iter().map(String::as_str).collect();
+
+        let mut edges: Vec<(String, String, String)> = self
+            .store
+            .channels()
+            .values()?
+            .iter()
+            .filter(|c| known.contains(c.from_node_id.as_str()) && known.contains(c.to_node_id.as_str()))
+            .map(|c| (c.id.clone(), c.from_node_id.clone(), c.to_node_id.clone()))
+            .collect();
+        edges.sort();
+
+        let mut adjacency: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+        for (channel_id, from, to) in &edges {
+            adjacency
+                .entry(from.as_str())
+                .or_default()
+                .push((channel_id.as_str(), to.as_str()));
+        }
+
+        let broken = find_back_edges(&node_ids, &adjacency);
+
+        let mut indegree: HashMap<&str, usize> = node_ids.iter().map(|id| (id.as_str(), 0)).collect();
+        for (channel_id, _, to) in &edges {
+            if !broken.contains(channel_id.as_str()) {
+                *indegree.entry(to.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        let mut level: HashMap<&str, usize> = HashMap::new();
+        let mut queue: VecDeque<&str> = node_ids
+            .iter()
+            .map(String::as_str)
+            .filter(|id| indegree[id] == 0)
+            .collect();
+        while let Some(node_id) = queue.pop_front() {
+            let current = *level.entry(node_id).or_insert(0);
+            for (channel_id, to) in adjacency.get(node_id).into_iter().flatten() {
+                if broken.contains(channel_id) {
+                    continue;
+                }
+                let next = level.entry(to).or_insert(0);
+                *next = (*next).max(current + 1);
+                let remaining = indegree.get_mut(to).expect("edge endpoint is a known node");
+                *remaining -= 1;
+                if *remaining == 0 {
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        let mut grouped: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for node_id in &node_ids {
+            let depth = level.get(node_id.as_str()).copied().unwrap_or(0);
+            grouped.entry(depth).or_default().push(node_id.clone());
+        }
+
+        let layers = grouped
+            .into_iter()
+            .map(|(index, node_ids)| Layer {
+                index,
+                name: format!("layer_{}", index),
+                node_ids,
+            })
+            .collect();
+
+        let mut broken_channels: Vec<String> = broken.into_iter().map(str::to_string).collect();
+        broken_channels.sort();
+        if !broken_channels.is_empty() {
+            tracing::warn!("Broke {} channels to layer a cyclic graph", broken_channels.len());
+        }
+
+        Ok(LayerInference {
+            layers,
+            broken_channels,
+        })
+    }
+
+    /// Checks every channel against `rules` using the inferred layers
+    /// (`layer_0`, `layer_1`, ...). Returns an empty vec when clean.
+    pub fn check_layer_rules(&self, rules: &[LayerRule]) -> anyhow::Result<Vec<Violation>> {
+        self.check_layer_rules_with(&self.infer_layers()?.layers, rules)
+    }
+
+    /// Like [`check_layer_rules`](Self::check_layer_rules), but against
+    /// caller-supplied layers, e.g. inferred layers renamed to `api`/`data`.
+    pub fn check_layer_rules_with(
+        &self,
+        layers: &[Layer],
+        rules: &[LayerRule],
+    ) -> anyhow::Result<Vec<Violation>> {
+        let layer_of: HashMap<&str, &str> = layers
+            .iter()
+            .flat_map(|layer| {
+                layer
+                    .node_ids
+                    .iter()
+                    .map(move |node_id| (node_id.as_str(), layer.name.as_str()))
+            })
+            .collect();
+
+        let mut violations: Vec<Violation> = self
+            .store
+            .channels()
+            .values()?
+            .iter()
+            .filter_map(|channel| {
+                let from_layer = layer_of.get(channel.from_node_id.as_str())?;
+                let to_layer = layer_of.get(channel.to_node_id.as_str())?;
+                let rule = rules
+                    .iter()
+                    .find(|r| r.from_layer == *from_layer && r.to_layer == *to_layer)?;
+                Some(Violation {
+                    rule: rule.clone(),
+                    channel_id: channel.id.clone(),
+                    from_node_id: channel.from_node_id.clone(),
+                    to_node_id: channel.to_node_id.clone(),
+                })
+            })
+            .collect();
+
+        violations.sort_by(|a, b| a.channel_id.cmp(&b.channel_id));
+        Ok(violations)
+    }
+
+    /// Registers one `ClusterType::Architectural` cluster per layer and
+    /// returns the new cluster ids in layer order.
+    pub fn register_layer_clusters(&self, layers: &[Layer]) -> anyhow::Result<Vec<String>> {
+        let mut ids = Vec::with_capacity(layers.len());
+        for layer in layers {
+            let mut cluster = Cluster::new(layer.name.clone(), ClusterType::Architectural);
+            cluster.id = self.next_id();
+            for node_id in &layer.node_ids {
+                cluster.add_node(node_id.clone());
+            }
+            let id = cluster.id.clone();
+            for observer in &self.observers {
+                observer.on_cluster_formed(&cluster);
+            }
+            self.store.clusters().insert(id.clone(), cluster)?;
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+}
+
+/// Channels that close a cycle, found as back edges of an iterative DFS.
+fn find_back_edges<'a>(
+    node_ids: &'a [String],
+    adjacency: &HashMap<&'a str, Vec<(&'a str, &'a str)>>,
+) -> HashSet<&'a str> {
+    const ON_STACK: u8 = 1;
+    const DONE: u8 = 2;
+
+    let mut state: HashMap<&str, u8> = HashMap::new();
+    let mut back_edges = HashSet::new();
+
+    for root in node_ids {
+        if state.contains_key(root.as_str()) {
+            continue;
+        }
+        state.insert(root.as_str(), ON_STACK);
+        let mut stack: Vec<(&str, usize)> = vec![(root.as_str(), 0)];
+
+        while let Some((node_id, next_edge)) = stack.last_mut() {
+            let outgoing = adjacency.get(node_id).map(Vec::as_slice).unwrap_or(&[]);
+            if let Some((channel_id, to)) = outgoing.get(*next_edge) {
+                *next_edge += 1;
+                match state.get(to) {
+                    Some(&ON_STACK) => {
+                        back_edges.insert(*channel_id);
+                    }
+                    Some(_) => {}
+                    None => {
+                        state.insert(to, ON_STACK);
+                        stack.push((to, 0));
+                    }
+                }
+            } else {
+                state.insert(node_id, DONE);
+                stack.pop();
+            }
+        }
+    }
+
+    back_edges
+}
@@ -0,0 +1,135 @@
+use crate::{engine::NeuroNodePathEngine, utils::DeterministicSource};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Algorithm used by [`NeuroNodePathEngine::compute_layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutAlgorithm {
+    /// Fruchterman-Reingold in the xy plane; every z coordinate is 0.
+    ForceDirected2D { iterations: usize },
+    /// Fruchterman-Reingold in three dimensions.
+    ForceDirected3D { iterations: usize },
+}
+
+impl NeuroNodePathEngine {
+    /// Positions every node by `algorithm` over the channel graph, stores the
+    /// result in each node's `position` and returns it keyed by node id.
+    ///
+    /// Starting positions are drawn from a [`DeterministicSource`] seeded with
+    /// `config.seed` (0 when unset), so the same graph always gets the same
+    /// layout.
+    pub fn compute_layout(
+        &self,
+        algorithm: LayoutAlgorithm,
+    ) -> anyhow::Result<HashMap<String, [f64; 3]>> {
+        let (dimensions, iterations) = match algorithm {
+            LayoutAlgorithm::ForceDirected2D { iterations } => (2, iterations),
+            LayoutAlgorithm::ForceDirected3D { iterations } => (3, iterations),
+        };
+
+        let mut ids: Vec<String> =
+            self.store.nodes().values()?.into_iter().map(|n| n.id).collect();
+        ids.sort();
+        let index: HashMap<&str, usize> =
+            ids.iter().enumerate().map(|(i, id)| (id.as_str(), i)).collect();
+        let mut edges: Vec<(usize, usize)> = self
+            .store
+            .channels()
+            .values()?
+            .iter()
+            .filter_map(|c| {
+                let from = *index.get(c.from_node_id.as_str())?;
+                let to = *index.get(c.to_node_id.as_str())?;
+                (from != to).then_some((from, to))
+            })
+            .collect();
+        edges.sort();
+
+        let positions = fruchterman_reingold(
+            ids.len(),
+            &edges,
+            dimensions,
+            iterations,
+            self.config.seed.unwrap_or(0),
+        );
+
+        let layout: HashMap<String, [f64; 3]> = ids.into_iter().zip(positions).collect();
+        for (id, position) in &layout {
+            self.store.nodes().update(id, &mut |node| node.position = Some(*position))?;
+        }
+        Ok(layout)
+    }
+}
+
+/// Classic Fruchterman-Reingold with an ideal edge length of 1 and a
+/// temperature that cools linearly to zero. Nodes start in a cube (or
+/// square) whose volume grows with the node count.
+fn fruchterman_reingold(
+    count: usize,
+    edges: &[(usize, usize)],
+    dimensions: usize,
+    iterations: usize,
+    seed: u64,
+) -> Vec<[f64; 3]> {
+    const K: f64 = 1.0;
+    const MIN_DISTANCE: f64 = 1e-6;
+
+    let side = (count.max(1) as f64).powf(1.0 / dimensions as f64);
+    let mut source = DeterministicSource::new(seed);
+    let mut positions: Vec<[f64; 3]> = (0..count)
+        .map(|_| {
+            let mut p = [0.0; 3];
+            for axis in p.iter_mut().take(dimensions) {
+                *axis = source.next_f64() * side;
+            }
+            p
+        })
+        .collect();
+
+    let start_temperature = side / 10.0;
+    for step in 0..iterations {
+        let temperature = start_temperature * (1.0 - step as f64 / iterations as f64);
+        let mut displacement = vec![[0.0; 3]; count];
+
+        for i in 0..count {
+            for j in (i + 1)..count {
+                let (delta, distance) = offset(&positions[i], &positions[j]);
+                let force = K * K / distance.max(MIN_DISTANCE);
+                for axis in 0..dimensions {
+                    let push = delta[axis] / distance.max(MIN_DISTANCE) * force;
+                    displacement[i][axis] += push;
+                    displacement[j][axis] -= push;
+                }
+            }
+        }
+
+        for &(from, to) in edges {
+            let (delta, distance) = offset(&positions[from], &positions[to]);
+            let force = distance * distance / K;
+            for axis in 0..dimensions {
+                let pull = delta[axis] / distance.max(MIN_DISTANCE) * force;
+                displacement[from][axis] -= pull;
+                displacement[to][axis] += pull;
+            }
+        }
+
+        for (position, moved) in positions.iter_mut().zip(&displacement) {
+            let length = moved.iter().map(|d| d * d).sum::<f64>().sqrt();
+            if length < MIN_DISTANCE {
+                continue;
+            }
+            let scale = length.min(temperature) / length;
+            for axis in 0..dimensions {
+                position[axis] += moved[axis] * scale;
+            }
+        }
+    }
+    positions
+}
+
+/// Vector from `b` to `a` and its length.
+fn offset(a: &[f64; 3], b: &[f64; 3]) -> ([f64; 3], f64) {
+    let delta = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    let distance = delta.iter().map(|d| d * d).sum::<f64>().sqrt();
+    (delta, distance)
+}
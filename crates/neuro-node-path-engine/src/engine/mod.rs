@@ -2,21 +2,71 @@ pub mod query_processor;
 pub mod path_resolver;
 pub mod audit_trail;
 pub mod cluster_navigator;
+pub mod pipeline;
+pub mod layers;
+pub mod explain;
+pub mod simulation;
+pub mod interfaces;
+pub mod routing;
+pub mod query_parser;
+pub mod name_index;
+pub mod repositories;
+pub mod observer;
+pub mod feedback;
+pub mod tags;
+pub mod layout;
+pub mod neighborhood;
+pub mod smells;
+pub mod stats;
+pub mod diff;
+pub mod flow;
+pub mod export;
+pub mod health;
+pub mod owners;
+pub mod debt;
+pub mod delta;
+pub mod modules;
+pub mod incremental;
 
 pub use query_processor::QueryProcessor;
-pub use path_resolver::PathResolver;
+pub use path_resolver::{PathResolver, ResolvedPath};
 pub use audit_trail::AuditTrail;
 pub use cluster_navigator::ClusterNavigator;
+pub use pipeline::{QueryStage, QueryState};
+pub use explain::{PathStep, StepReason};
+pub use layers::{Layer, LayerInference, LayerRule, Violation};
+pub use routing::WeightedPath;
+pub use query_parser::{QueryParser, StructuredQuery};
+pub use name_index::NameIndex;
+pub use observer::EngineObserver;
+pub use layout::LayoutAlgorithm;
+pub use neighborhood::Neighborhood;
+pub use smells::{Degree, SmellKind, StructuralSmell};
+pub use stats::RepositoryStats;
+pub use diff::{diff_results, Change, QueryResultDiff};
+pub use export::GraphFormat;
+pub use health::HealthScore;
+pub use delta::{GraphDelta, GraphSnapshot};
 
 use crate::{
-    core::{Node, Cluster, NeuralChannel, Interface},
-    i18n::KnotenlexikonStore,
-    validation::DualPathValidator,
-    EngineConfig,
+    core::{cluster::ClusterType, node::NodeType, Node, Cluster, NeuralChannel, Interface, Neuron},
+    i18n::{KnotenlexikonStore, Translator},
+    repository::{
+        extract_debt_markers, extract_documentation, ClassifierRegistry, CodeAnalyzer,
+        NodeClassifier,
+    },
+    utils::{normalize_name, DeterministicSource},
+    store::{GraphStore, InMemoryGraphStore},
+    EngineConfig, EngineError,
 };
+use anyhow::Context;
+use debt::DEBT_MARKERS_PROPERTY;
 use dashmap::DashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, Clone)]
 pub struct QueryContext {
@@ -24,120 +74,734 @@ pub struct QueryContext {
     pub language: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub request_id: String,
+    /// Number of leading `node_path` and interface entries to skip in the result.
+    pub offset: usize,
+    /// Maximum number of `node_path` and interface entries to return.
+    pub limit: Option<usize>,
+    /// Once passed, path resolution stops and the query returns the partial
+    /// path found so far. Defaults to now plus [`EngineConfig::query_timeout`].
+    pub deadline: Option<std::time::Instant>,
+}
+
+impl QueryContext {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            language: "en".to_string(),
+            timestamp: chrono::Utc::now(),
+            request_id: uuid::Uuid::new_v4().to_string(),
+            offset: 0,
+            limit: None,
+            deadline: None,
+        }
+    }
+}
+
+/// Phase reported by [`NeuroNodePathEngine::index_repository_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum IndexPhase {
+    Reading,
+    Clustering,
+}
+
+/// Outcome of an index run. Problem files are still indexed as nodes.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct IndexReport {
+    pub files_indexed: usize,
+    /// Files with invalid UTF-8, analyzed after lossy decoding.
+    pub lossy_files: Vec<String>,
+    /// Files that could not be read, indexed by path only.
+    pub unreadable_files: Vec<String>,
+    /// Files excluded by the repository's depth limit.
+    pub files_skipped: usize,
+    /// The repository matched the last completed index and was skipped.
+    pub unchanged: bool,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct Progress {
+    pub phase: IndexPhase,
+    pub current: usize,
+    pub total: usize,
 }
 
 pub struct NeuroNodePathEngine {
     config: EngineConfig,
-    nodes: Arc<DashMap<String, Node>>,
-    clusters: Arc<DashMap<String, Cluster>>,
-    channels: Arc<DashMap<String, NeuralChannel>>,
-    interfaces: Arc<DashMap<String, Interface>>,
-    audit_trail: Arc<RwLock<AuditTrail>>,
-    path_resolver: PathResolver,
-    query_processor: QueryProcessor,
-    cluster_navigator: ClusterNavigator,
+    store: Arc<dyn GraphStore>,
+    neurons: Arc<DashMap<String, Neuron>>,
+    audit_trail: Arc<parking_lot::RwLock<AuditTrail>>,
+    pipeline: Vec<Box<dyn QueryStage>>,
+    classifiers: ClassifierRegistry,
+    observers: Vec<Box<dyn EngineObserver>>,
+    deterministic: Option<parking_lot::Mutex<DeterministicSource>>,
+    /// Root and fingerprint of the last completed index run per repository
+    /// namespace; `None` is the default, unnamespaced run.
+    last_index: HashMap<Option<String>, (PathBuf, String)>,
+    /// Index over all node names, rebuilt on the first query after nodes change.
+    name_index: parking_lot::RwLock<Option<Arc<NameIndex>>>,
     knotenlexikon: Arc<RwLock<KnotenlexikonStore>>,
-    dual_path_validator: DualPathValidator,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct QueryResult {
     pub request_id: String,
     pub query: String,
     pub node_path: Vec<String>,
     pub cluster_path: Vec<String>,
     pub channel_interfaces: Vec<String>,
+    /// Length of the full `node_path` before pagination.
+    pub total_nodes: usize,
+    /// Length of the full `channel_interfaces` before pagination.
+    pub total_interfaces: usize,
     pub audit_path: Vec<String>,
+    /// Language the query was answered in; see [`NeuroNodePathEngine::query_in`].
+    #[serde(default)]
+    pub language: String,
+    /// `explanation_en` or `explanation_de`, whichever matches `language`.
+    #[serde(default)]
+    pub explanation: String,
+    /// Why the result is not fully in the requested language, if it is not.
+    #[serde(default)]
+    pub language_note: Option<String>,
     pub explanation_en: String,
     pub explanation_de: String,
     pub validation_status: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Path resolution hit the deadline; `node_path` holds what was found.
+    #[serde(default)]
+    pub partial: bool,
+    #[serde(default)]
+    pub partial_reason: Option<String>,
+    /// The parsed query, for [`NeuroNodePathEngine::query_structured`].
+    #[serde(default)]
+    pub structured: Option<StructuredQuery>,
 }
 
 impl NeuroNodePathEngine {
     pub fn new(config: EngineConfig) -> anyhow::Result<Self> {
+        Self::with_store(config, Arc::new(InMemoryGraphStore::new()))
+    }
+
+    /// An engine over `store` instead of the default in-memory maps. Nodes
+    /// already in the store are queryable right away.
+    pub fn with_store(config: EngineConfig, store: Arc<dyn GraphStore>) -> anyhow::Result<Self> {
+        let pipeline = pipeline::default_pipeline(&config)?;
+        let deterministic = config
+            .seed
+            .map(|seed| parking_lot::Mutex::new(DeterministicSource::new(seed)));
         Ok(Self {
             config,
-            nodes: Arc::new(DashMap::new()),
-            clusters: Arc::new(DashMap::new()),
-            channels: Arc::new(DashMap::new()),
-            interfaces: Arc::new(DashMap::new()),
-            audit_trail: Arc::new(RwLock::new(AuditTrail::new())),
-            path_resolver: PathResolver::new(),
-            query_processor: QueryProcessor::new(),
-            cluster_navigator: ClusterNavigator::new(),
+            store,
+            neurons: Arc::new(DashMap::new()),
+            audit_trail: Arc::new(parking_lot::RwLock::new(AuditTrail::new())),
+            pipeline,
+            classifiers: ClassifierRegistry::new(),
+            observers: Vec::new(),
+            deterministic,
+            last_index: HashMap::new(),
+            name_index: parking_lot::RwLock::new(None),
             knotenlexikon: Arc::new(RwLock::new(KnotenlexikonStore::default())),
-            dual_path_validator: DualPathValidator::new(),
         })
     }
 
+    /// Id for an object the engine creates: seeded when `config.seed` is set,
+    /// a random UUID otherwise.
+    pub(crate) fn next_id(&self) -> String {
+        match &self.deterministic {
+            Some(source) => source.lock().next_id(),
+            None => uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// Replaces the query pipeline. Start from [`pipeline::default_pipeline`]
+    /// to reorder the built-in stages or insert custom ones.
+    pub fn set_pipeline(&mut self, stages: Vec<Box<dyn QueryStage>>) {
+        self.pipeline = stages;
+    }
+
+    /// Adds a classifier consulted while indexing, before the configured
+    /// config-file set and the `File` fallback. Higher priorities run first.
+    pub fn register_classifier(&mut self, priority: i32, classifier: Box<dyn NodeClassifier>) {
+        self.classifiers.register(priority, classifier);
+    }
+
+    /// Adds an observer; every registered observer receives every event.
+    pub fn add_observer(&mut self, observer: Box<dyn EngineObserver>) {
+        self.observers.push(observer);
+    }
+
+    pub fn pipeline_stage_names(&self) -> Vec<&str> {
+        self.pipeline.iter().map(|stage| stage.name()).collect()
+    }
+
+    /// The configuration the engine was built with, e.g. for
+    /// [`pipeline::default_pipeline`] when customizing the stages.
+    pub fn config(&self) -> &EngineConfig {
+        &self.config
+    }
+
     pub fn set_lemma_store(&mut self, store: KnotenlexikonStore) {
         self.knotenlexikon = Arc::new(RwLock::new(store));
     }
 
+    /// Does the one-off work a first index or query would otherwise pay for:
+    /// compiles every pattern set, fills an empty lexicon with the default
+    /// lemmas and builds the node-name index. Safe to call repeatedly.
+    pub async fn warm(&self) -> anyhow::Result<()> {
+        CodeAnalyzer::warm()?;
+        {
+            let mut lexicon = self.knotenlexikon.write().await;
+            if lexicon.get_all_entries().is_empty() {
+                *lexicon = KnotenlexikonStore::new();
+            }
+        }
+        self.name_index()?;
+        Ok(())
+    }
+
+    /// "You might also search" suggestions for `query` from the lexicon's
+    /// related concepts. See [`KnotenlexikonStore::suggest_related`].
+    pub async fn suggest_related(&self, query: &str) -> Vec<String> {
+        self.knotenlexikon.read().await.suggest_related(query)
+    }
+
+    /// Signs all future audit entries with `key`. Call again to rotate keys.
+    pub fn set_audit_signing_key(&self, key: ed25519_dalek::SigningKey, key_note: Option<String>) {
+        self.audit_trail.write().set_signing_key(key, key_note);
+    }
+
+    /// Live feed of audit entries logged from now on.
+    pub fn subscribe_audit(&self) -> audit_trail::AuditSubscription {
+        audit_trail::AuditSubscription::new(self.audit_trail.read().subscribe())
+    }
+
+    pub fn audit_entries(&self) -> Vec<audit_trail::AuditEntry> {
+        self.audit_trail.read().get_entries().to_vec()
+    }
+
+    pub fn verify_audit_integrity(&self) -> bool {
+        self.audit_trail.read().verify_integrity()
+    }
+
+    pub fn add_node(&self, mut node: Node) -> anyhow::Result<String> {
+        node.normalized_name = normalize_name(&node.name);
+        let id = node.id.clone();
+        self.store.nodes().insert(id.clone(), node)?;
+        *self.name_index.write() = None;
+        Ok(id)
+    }
+
+    pub fn get_node(&self, node_id: &str) -> anyhow::Result<Option<Node>> {
+        Ok(self.store.nodes().get(node_id)?)
+    }
+
+    pub fn node_count(&self) -> anyhow::Result<usize> {
+        Ok(self.store.nodes().len()?)
+    }
+
+    pub fn add_channel(&self, channel: NeuralChannel) -> anyhow::Result<String> {
+        let id = channel.id.clone();
+        self.store.channels().insert(id.clone(), channel)?;
+        Ok(id)
+    }
+
+    pub fn get_channel(&self, channel_id: &str) -> anyhow::Result<Option<NeuralChannel>> {
+        Ok(self.store.channels().get(channel_id)?)
+    }
+
+    pub fn add_neuron(&self, neuron: Neuron) -> String {
+        let id = neuron.id.clone();
+        self.neurons.insert(id.clone(), neuron);
+        id
+    }
+
+    pub fn get_neuron(&self, neuron_id: &str) -> Option<Neuron> {
+        self.neurons.get(neuron_id).map(|n| n.clone())
+    }
+
+    pub fn register_interface(&self, interface: Interface) -> anyhow::Result<()> {
+        Ok(self.store.interfaces().insert(interface.id.clone(), interface)?)
+    }
+
+    pub fn get_interface(&self, interface_id: &str) -> anyhow::Result<Option<Interface>> {
+        Ok(self.store.interfaces().get(interface_id)?)
+    }
+
+    pub fn add_cluster(&self, cluster: Cluster) -> anyhow::Result<String> {
+        let id = cluster.id.clone();
+        self.store.clusters().insert(id.clone(), cluster)?;
+        Ok(id)
+    }
+
+    pub fn get_cluster(&self, cluster_id: &str) -> anyhow::Result<Option<Cluster>> {
+        Ok(self.store.clusters().get(cluster_id)?)
+    }
+
+    /// Summaries of `cluster_id` keyed by each of the configured
+    /// `supported_languages`. See [`ClusterNavigator::summarize_in`].
+    pub fn cluster_summaries(
+        &self,
+        cluster_id: &str,
+    ) -> anyhow::Result<Option<HashMap<String, String>>> {
+        let Some(cluster) = self.get_cluster(cluster_id)? else {
+            return Ok(None);
+        };
+        let mut members: HashMap<String, Node> = HashMap::new();
+        for id in &cluster.node_ids {
+            if let Some(node) = self.get_node(id)? {
+                members.insert(id.clone(), node);
+            }
+        }
+        let navigator = ClusterNavigator::new().with_stop_words(self.config.stop_words.clone());
+        let translator = Translator::new();
+        Ok(Some(
+            self.config
+                .supported_languages
+                .iter()
+                .map(|language| {
+                    let summary = navigator.summarize_in(&cluster, &members, &translator, language);
+                    (language.clone(), summary)
+                })
+                .collect(),
+        ))
+    }
+
+    /// Cluster ids grouped by cluster type, each group sorted.
+    pub fn clusters_by_type(&self) -> anyhow::Result<HashMap<ClusterType, Vec<String>>> {
+        let mut groups: HashMap<ClusterType, Vec<String>> = HashMap::new();
+        self.store.clusters().for_each(&mut |cluster| {
+            groups
+                .entry(cluster.cluster_type.clone())
+                .or_default()
+                .push(cluster.id.clone());
+        })?;
+        for ids in groups.values_mut() {
+            ids.sort();
+        }
+        Ok(groups)
+    }
+
     pub async fn index_repository(
         &mut self,
         repository: &crate::repository::CodeRepository,
-    ) -> anyhow::Result<()> {
-        let files = repository.scan_files().await?;
-        
-        for file in files {
-            let node = Node::new(
-                file.clone(),
-                crate::core::node::NodeType::File,
-                file,
-            );
-            self.nodes.insert(node.id.clone(), node);
+    ) -> anyhow::Result<IndexReport> {
+        self.index_repository_with_progress(repository, |_| {}).await
+    }
+
+    /// Indexes a repository, reporting each file read and each cluster formed
+    /// to `progress`.
+    pub async fn index_repository_with_progress(
+        &mut self,
+        repository: &crate::repository::CodeRepository,
+        progress: impl Fn(Progress),
+    ) -> anyhow::Result<IndexReport> {
+        self.index_repository_cancellable(repository, progress, &CancellationToken::new())
+            .await
+    }
+
+    /// Indexes a repository until `cancel` fires, checked between files and
+    /// clusters. A cancelled run returns [`EngineError::Cancelled`] and keeps
+    /// whatever was indexed before the cancellation.
+    ///
+    /// Re-indexing a repository whose fingerprint matches the last completed
+    /// run is a no-op; a changed one replaces the nodes, clusters and
+    /// channels of the default namespace, which includes nodes added
+    /// without a `repo_id`.
+    ///
+    /// A run that would exceed `max_nodes` fails with
+    /// [`EngineError::GraphTooLarge`] before adding anything. Channels are
+    /// known only once every file is read, so a run that would exceed
+    /// `max_channels` fails then and discards the nodes it added.
+    pub async fn index_repository_cancellable(
+        &mut self,
+        repository: &crate::repository::CodeRepository,
+        progress: impl Fn(Progress),
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<IndexReport> {
+        self.index_into(None, repository, progress, cancel).await
+    }
+
+    /// Indexes `repository`, tagging its nodes and clusters with `repo_id`.
+    /// A changed repository replaces what its namespace held before, so
+    /// re-indexing never duplicates the graph.
+    pub(crate) async fn index_into(
+        &mut self,
+        repo_id: Option<&str>,
+        repository: &crate::repository::CodeRepository,
+        progress: impl Fn(Progress),
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<IndexReport> {
+        let fingerprint = repository.fingerprint().await?;
+        let current = (repository.get_root_path().clone(), fingerprint);
+        let namespace = repo_id.map(str::to_string);
+        if self.last_index.get(&namespace) == Some(&current) {
+            tracing::info!("Repository unchanged since last index, skipping");
+            return Ok(IndexReport {
+                unchanged: true,
+                ..IndexReport::default()
+            });
+        }
+
+        let scan = repository.scan().await?;
+        let files = scan.files;
+        let total = files.len();
+        self.check_graph_limits(
+            self.store.nodes().len()? - self.namespace_len(repo_id)? + total,
+            self.store.channels().len()?,
+        )?;
+        self.clear_namespace(repo_id)?;
+        let analyzer = CodeAnalyzer::try_new()?;
+
+        let mut report = IndexReport {
+            files_skipped: scan.skipped,
+            ..IndexReport::default()
+        };
+        let mut by_directory: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut node_ids: HashMap<String, String> = HashMap::new();
+        let mut module_declarations = Vec::new();
+        for (index, file) in files.into_iter().enumerate() {
+            if cancel.is_cancelled() {
+                return Err(EngineError::Cancelled.into());
+            }
+            let content = read_source(&file, &mut report).await;
+            let mut node = self.file_node(&file, namespace.as_deref(), content.as_deref());
+            node.id = self.next_id();
+            let directory = Path::new(&file)
+                .parent()
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let node_id = node.id.clone();
+            by_directory.entry(directory).or_default().push(node_id.clone());
+            for observer in &self.observers {
+                observer.on_node_indexed(&node);
+            }
+            self.store.nodes().insert(node_id.clone(), node)?;
+            *self.name_index.write() = None;
+            if let Some(code) = content.as_deref().filter(|_| file.ends_with(".rs")) {
+                self.discover_interfaces(&analyzer, &node_id, &file, code)?;
+                let modules = analyzer.module_declarations(code);
+                if !modules.is_empty() {
+                    module_declarations.push((node_id.clone(), file.clone(), modules));
+                }
+            }
+            node_ids.insert(file.clone(), node_id.clone());
+            report.files_indexed += 1;
+
+            progress(Progress {
+                phase: IndexPhase::Reading,
+                current: index + 1,
+                total,
+            });
+        }
+
+        let channels = self.module_channels(&module_declarations, &node_ids);
+        let limits = self.check_graph_limits(
+            self.store.nodes().len()?,
+            self.store.channels().len()? + channels.len(),
+        );
+        if let Err(e) = limits {
+            self.clear_namespace(repo_id)?;
+            return Err(e.into());
+        }
+        for channel in channels {
+            self.add_channel(channel)?;
+        }
+
+        if self.config.enable_clustering {
+            self.cluster_by_directory(repo_id, by_directory, &progress, cancel)?;
         }
 
+        self.last_index.insert(namespace, current);
+        Ok(report)
+    }
+
+    /// Fails if a graph of `nodes` nodes and `channels` channels would
+    /// exceed `config.max_nodes` or `config.max_channels`.
+    fn check_graph_limits(&self, nodes: usize, channels: usize) -> Result<(), EngineError> {
+        if let Some(limit) = self.config.max_nodes.filter(|limit| nodes > *limit) {
+            return Err(EngineError::GraphTooLarge { kind: "nodes", limit, actual: nodes });
+        }
+        if let Some(limit) = self.config.max_channels.filter(|limit| channels > *limit) {
+            return Err(EngineError::GraphTooLarge { kind: "channels", limit, actual: channels });
+        }
         Ok(())
     }
 
+    fn namespace_len(&self, namespace: Option<&str>) -> Result<usize, EngineError> {
+        let mut len = 0;
+        self.store.nodes().for_each(&mut |n| {
+            if n.repo_id.as_deref() == namespace {
+                len += 1;
+            }
+        })?;
+        Ok(len)
+    }
+
+    /// The node for `file` in `namespace`, classified and annotated from
+    /// `content`. The caller assigns its id.
+    fn file_node(&self, file: &str, namespace: Option<&str>, content: Option<&str>) -> Node {
+        let node_type = self.classify_file(file, content);
+        let mut node = Node::new(file.to_string(), node_type, file.to_string());
+        node.repo_id = namespace.map(str::to_string);
+        node.metadata.documentation = content.and_then(|code| extract_documentation(file, code));
+        if let Some(code) = content {
+            let markers = extract_debt_markers(code, &self.config.debt_markers);
+            if !markers.is_empty() {
+                node.metadata
+                    .properties
+                    .insert(DEBT_MARKERS_PROPERTY.to_string(), serde_json::json!(markers));
+            }
+        }
+        node
+    }
+
+    /// Unreadable files (`content` is `None`) are classified by path alone.
+    fn classify_file(&self, path: &str, content: Option<&str>) -> NodeType {
+        if let Some(node_type) = self.classifiers.classify(path, content.unwrap_or_default()) {
+            return node_type;
+        }
+
+        if self.config.config_files.is_config(path) {
+            NodeType::Config
+        } else {
+            NodeType::File
+        }
+    }
+
+    /// Groups file nodes into one functional cluster per directory, named
+    /// after the directory's last path component.
+    fn cluster_by_directory(
+        &self,
+        repo_id: Option<&str>,
+        by_directory: BTreeMap<String, Vec<String>>,
+        progress: &impl Fn(Progress),
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<()> {
+        let total = by_directory.len();
+        let nodes: HashMap<String, Node> = self
+            .store
+            .nodes()
+            .values()?
+            .into_iter()
+            .map(|node| (node.id.clone(), node))
+            .collect();
+
+        for (index, (directory, node_ids)) in by_directory.into_iter().enumerate() {
+            if cancel.is_cancelled() {
+                return Err(EngineError::Cancelled.into());
+            }
+            let name = Path::new(&directory)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or(directory);
+            let mut cluster = Cluster::new(name, ClusterType::Functional);
+            cluster.id = self.next_id();
+            cluster.repo_id = repo_id.map(str::to_string);
+            for node_id in node_ids {
+                cluster.add_node(node_id);
+            }
+            cluster.compute_cohesion(&nodes);
+            for observer in &self.observers {
+                observer.on_cluster_formed(&cluster);
+            }
+            self.store.clusters().insert(cluster.id.clone(), cluster)?;
+
+            progress(Progress {
+                phase: IndexPhase::Clustering,
+                current: index + 1,
+                total,
+            });
+        }
+        Ok(())
+    }
+
+    fn name_index(&self) -> anyhow::Result<Arc<NameIndex>> {
+        if let Some(index) = self.name_index.read().as_ref() {
+            return Ok(index.clone());
+        }
+        let names: Vec<String> = if self.config.normalize_names {
+            self.store
+                .nodes()
+                .values()?
+                .iter()
+                .map(|n| format!(" {} ", n.normalized_name))
+                .collect()
+        } else {
+            self.store.nodes().values()?.into_iter().map(|n| n.name).collect()
+        };
+        let index = Arc::new(NameIndex::build(names.iter().map(String::as_str))?);
+        *self.name_index.write() = Some(index.clone());
+        Ok(index)
+    }
+
     pub async fn query(&self, query_str: &str) -> anyhow::Result<QueryResult> {
+        self.run_query(QueryContext::new(query_str), |_| true, None, &CancellationToken::new())
+    }
+
+    /// Runs a query and returns at most `limit` path nodes and interfaces,
+    /// starting at `offset`. The totals in the result and the audit entry
+    /// cover the full, unpaginated result.
+    pub async fn query_paged(
+        &self,
+        query_str: &str,
+        offset: usize,
+        limit: usize,
+    ) -> anyhow::Result<QueryResult> {
         let context = QueryContext {
-            query: query_str.to_string(),
-            language: "en".to_string(),
-            timestamp: chrono::Utc::now(),
-            request_id: uuid::Uuid::new_v4().to_string(),
+            offset,
+            limit: Some(limit),
+            ..QueryContext::new(query_str)
         };
+        self.run_query(context, |_| true, None, &CancellationToken::new())
+    }
 
-        let nodes_snapshot: Vec<_> = self.nodes
-            .iter()
-            .map(|ref_multi| ref_multi.clone())
-            .collect();
+    /// Runs a query written in the [`QueryParser`] syntax, e.g.
+    /// `type:function cluster:service name~user`. The path starts from every
+    /// node passing the filters, narrowed to those named in the free text
+    /// when there is any.
+    pub async fn query_structured(&self, input: &str) -> anyhow::Result<QueryResult> {
+        let structured = QueryParser::parse(input)?;
+        let cluster_members: Option<HashSet<String>> = match &structured.cluster_filter {
+            Some(name) => Some(
+                self.store
+                    .clusters()
+                    .filter(&mut |c| &c.name == name)?
+                    .into_iter()
+                    .flat_map(|c| c.node_ids)
+                    .collect(),
+            ),
+            None => None,
+        };
+        let keep = |node: &Node| {
+            structured.matches(node)
+                && cluster_members.as_ref().is_none_or(|m| m.contains(&node.id))
+        };
+        self.run_query(
+            QueryContext::new(input),
+            keep,
+            Some(structured.clone()),
+            &CancellationToken::new(),
+        )
+    }
 
-        let node_path = self.path_resolver.resolve(&context, &nodes_snapshot)?;
-        let cluster_path = self.cluster_navigator.navigate(&context, &self.clusters)?;
-        
-        let channel_interfaces: Vec<String> = self.channels
-            .iter()
-            .map(|ref_multi| ref_multi.id.clone())
-            .collect();
+    /// Runs a query until `cancel` fires, checked between pipeline stages and
+    /// during path resolution. A cancelled query returns
+    /// [`EngineError::Cancelled`].
+    pub async fn query_cancellable(
+        &self,
+        query_str: &str,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<QueryResult> {
+        self.run_query(QueryContext::new(query_str), |_| true, None, cancel)
+    }
+
+    /// Runs a query answered in `language`, which picks the result's
+    /// `explanation`. A language outside `config.supported_languages` falls
+    /// back to the first supported one, noted in `language_note`.
+    pub async fn query_in(&self, query_str: &str, language: &str) -> anyhow::Result<QueryResult> {
+        let supported = &self.config.supported_languages;
+        let (language, note) = if supported.iter().any(|l| l == language) {
+            (language.to_string(), None)
+        } else {
+            let fallback = supported.first().cloned().unwrap_or_else(|| "en".to_string());
+            let note = format!("Language '{}' is not supported; answered in '{}'", language, fallback);
+            (fallback, Some(note))
+        };
+        let context = QueryContext {
+            language,
+            ..QueryContext::new(query_str)
+        };
+        let mut result = self.run_query(context, |_| true, None, &CancellationToken::new())?;
+        if note.is_some() {
+            result.language_note = note;
+        }
+        Ok(result)
+    }
+
+    /// Runs a query over every node whose type is not in `exclude`, e.g.
+    /// `&[NodeType::Config]` to keep manifests out of the resolved path.
+    pub async fn query_filtered(
+        &self,
+        query_str: &str,
+        exclude: &[NodeType],
+    ) -> anyhow::Result<QueryResult> {
+        self.run_query(
+            QueryContext::new(query_str),
+            |node| !exclude.contains(&node.node_type),
+            None,
+            &CancellationToken::new(),
+        )
+    }
 
-        let mut audit_trail = self.audit_trail.write().await;
-        let audit_path = audit_trail.log_query(&context, &node_path, &cluster_path)?;
+    fn run_query(
+        &self,
+        mut context: QueryContext,
+        keep: impl Fn(&Node) -> bool,
+        structured: Option<StructuredQuery>,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<QueryResult> {
+        if let Some(source) = &self.deterministic {
+            let mut source = source.lock();
+            context.request_id = source.next_id();
+            context.timestamp = source.next_timestamp();
+        }
+        let timestamp = context.timestamp;
+        if context.deadline.is_none() {
+            context.deadline = self
+                .config
+                .query_timeout
+                .map(|timeout| std::time::Instant::now() + timeout);
+        }
 
-        let (explanation_en, explanation_de) = self.generate_explanations(&node_path)?;
+        let mut state = QueryState::new(
+            context,
+            &keep,
+            self.store.as_ref(),
+            &self.audit_trail,
+        );
+        state.cancel = cancel.clone();
+        state.structured = structured;
+        state.name_index = Some(self.name_index()?);
 
-        let validation = self.dual_path_validator.validate(&node_path, &cluster_path)?;
+        for stage in &self.pipeline {
+            if cancel.is_cancelled() {
+                return Err(EngineError::Cancelled.into());
+            }
+            stage
+                .run(&mut state)
+                .with_context(|| format!("Query stage '{}' failed", stage.name()))?;
+        }
 
-        Ok(QueryResult {
-            request_id: context.request_id,
-            query: context.query,
-            node_path,
-            cluster_path,
-            channel_interfaces,
-            audit_path,
-            explanation_en,
-            explanation_de,
-            validation_status: validation,
-            timestamp: chrono::Utc::now(),
-        })
+        let mut result = state.into_result();
+        if self.deterministic.is_some() {
+            result.timestamp = timestamp;
+        }
+        for observer in &self.observers {
+            observer.on_query_executed(&result);
+            if result.validation_status.starts_with("INVALID") {
+                observer.on_validation_failed(&result);
+            }
+        }
+        Ok(result)
     }
+}
 
-    fn generate_explanations(&self, _node_path: &[String]) -> anyhow::Result<(String, String)> {
-        let en = "Query execution completed through neural node paths with cluster traversal and channel validation.".to_string();
-        let de = "Abfrageausführung über Neuroknotenpfade mit Clusterdurchquerung und Kanalvalidierung abgeschlossen.".to_string();
-        Ok((en, de))
+/// Reads `file` for indexing. Invalid UTF-8 is decoded lossily and an
+/// unreadable file gives `None`; both are recorded in `report`.
+async fn read_source(file: &str, report: &mut IndexReport) -> Option<String> {
+    match tokio::fs::read(file).await {
+        Ok(bytes) => Some(match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::warn!(path = %file, "File is not valid UTF-8, decoding lossily");
+                report.lossy_files.push(file.to_string());
+                String::from_utf8_lossy(e.as_bytes()).into_owned()
+            }
+        }),
+        Err(e) => {
+            tracing::warn!(path = %file, error = %e, "File is unreadable, indexing by path only");
+            report.unreadable_files.push(file.to_string());
+            None
+        }
     }
 }
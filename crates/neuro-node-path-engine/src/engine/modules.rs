@@ -0,0 +1,74 @@
+use crate::{
+    core::{channel::SignalType, NeuralChannel},
+    engine::NeuroNodePathEngine,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+impl NeuroNodePathEngine {
+    /// One `DependencyLink` channel from each Rust file to every file it
+    /// declares with `mod name;`. `declarations` holds `(node id, path,
+    /// module names)` per file and `node_ids` maps the paths of the files
+    /// just indexed to their nodes; declarations of files outside it are
+    /// ignored.
+    pub(crate) fn module_channels(
+        &self,
+        declarations: &[(String, String, Vec<String>)],
+        node_ids: &HashMap<String, String>,
+    ) -> Vec<NeuralChannel> {
+        let mut channels = Vec::new();
+        for (parent_id, path, modules) in declarations {
+            for module in modules {
+                let child = module_files(Path::new(path), module)
+                    .into_iter()
+                    .find_map(|candidate| node_ids.get(candidate.to_str()?));
+                if let Some(child_id) = child {
+                    let mut channel = NeuralChannel::new(
+                        parent_id.clone(),
+                        child_id.clone(),
+                        SignalType::DependencyLink,
+                    );
+                    channel.id = self.next_id();
+                    channels.push(channel);
+                }
+            }
+        }
+        channels
+    }
+}
+
+/// Where `mod module;` in `parent` may live: `module.rs` or
+/// `module/mod.rs`, next to `parent` for `lib.rs`, `main.rs` and `mod.rs`
+/// and in the directory named after `parent` otherwise.
+fn module_files(parent: &Path, module: &str) -> [PathBuf; 2] {
+    let directory = parent.parent().unwrap_or(Path::new(""));
+    let base = match parent.file_stem().and_then(|stem| stem.to_str()) {
+        Some("lib" | "main" | "mod") | None => directory.to_path_buf(),
+        Some(stem) => directory.join(stem),
+    };
+    [
+        base.join(format!("{}.rs", module)),
+        base.join(module).join("mod.rs"),
+    ]
+}
+
+/// Files whose `mod` declaration could refer to `child`: the inverse of
+/// [`module_files`].
+pub(crate) fn module_parents(child: &Path) -> Vec<PathBuf> {
+    let module_path = match child.file_name().and_then(|name| name.to_str()) {
+        Some("mod.rs") => child.parent().unwrap_or(Path::new("")),
+        _ => child,
+    };
+    let base = module_path.parent().unwrap_or(Path::new(""));
+    let mut parents: Vec<PathBuf> = ["lib.rs", "main.rs", "mod.rs"]
+        .iter()
+        .map(|name| base.join(name))
+        .filter(|parent| parent != child)
+        .collect();
+    if let Some(name) = base.file_name() {
+        let mut sibling = name.to_os_string();
+        sibling.push(".rs");
+        parents.push(base.with_file_name(sibling));
+    }
+    parents
+}
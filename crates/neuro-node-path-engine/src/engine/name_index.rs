@@ -0,0 +1,47 @@
+use aho_corasick::AhoCorasick;
+use std::collections::HashSet;
+
+/// Aho-Corasick automaton over distinct node names. Finds every name that
+/// occurs in a query in one pass over the query, matching the substring
+/// semantics of `query.contains(&node.name)`.
+pub struct NameIndex {
+    automaton: AhoCorasick,
+    names: Vec<String>,
+    /// `"".contains` is always true, so an empty name matches every query.
+    has_empty_name: bool,
+}
+
+impl NameIndex {
+    pub fn build<'a>(names: impl IntoIterator<Item = &'a str>) -> anyhow::Result<Self> {
+        let mut has_empty_name = false;
+        let mut distinct: HashSet<&str> = HashSet::new();
+        for name in names {
+            if name.is_empty() {
+                has_empty_name = true;
+            } else {
+                distinct.insert(name);
+            }
+        }
+
+        let names: Vec<String> = distinct.into_iter().map(str::to_string).collect();
+        let automaton = AhoCorasick::new(&names)?;
+        Ok(Self {
+            automaton,
+            names,
+            has_empty_name,
+        })
+    }
+
+    /// Names occurring anywhere in `query`, including overlapping ones.
+    pub fn matching_names(&self, query: &str) -> HashSet<&str> {
+        let mut matched: HashSet<&str> = self
+            .automaton
+            .find_overlapping_iter(query)
+            .map(|m| self.names[m.pattern().as_usize()].as_str())
+            .collect();
+        if self.has_empty_name {
+            matched.insert("");
+        }
+        matched
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    core::{NeuralChannel, Node},
+    engine::NeuroNodePathEngine,
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A node with the nodes and channels around it, from
+/// [`NeuroNodePathEngine::neighborhood`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Neighborhood {
+    pub node: Node,
+    /// Nodes within the radius other than `node`, sorted by id.
+    pub neighbors: Vec<Node>,
+    /// Channels between any two nodes of the neighborhood, sorted by id.
+    pub channels: Vec<NeuralChannel>,
+}
+
+impl NeuroNodePathEngine {
+    /// `node_id` and every node at most `radius` channel hops away, following
+    /// channels in either direction. Radius 0 yields the node alone. Returns
+    /// `None` if the node does not exist.
+    pub fn neighborhood(
+        &self,
+        node_id: &str,
+        radius: usize,
+    ) -> anyhow::Result<Option<Neighborhood>> {
+        let Some(node) = self.get_node(node_id)? else {
+            return Ok(None);
+        };
+
+        let mut adjacent: HashMap<String, Vec<String>> = HashMap::new();
+        for channel in self.store.channels().values()? {
+            let (from, to) = (channel.from_node_id.clone(), channel.to_node_id.clone());
+            adjacent.entry(from.clone()).or_default().push(to.clone());
+            adjacent.entry(to).or_default().push(from);
+        }
+
+        let mut reached: HashSet<String> = HashSet::from([node.id.clone()]);
+        let mut queue = VecDeque::from([(node.id.clone(), 0)]);
+        while let Some((current, distance)) = queue.pop_front() {
+            if distance == radius {
+                continue;
+            }
+            for next in adjacent.get(&current).into_iter().flatten() {
+                if self.store.nodes().contains(next)? && reached.insert(next.clone()) {
+                    queue.push_back((next.clone(), distance + 1));
+                }
+            }
+        }
+
+        let mut neighbors: Vec<Node> = Vec::new();
+        for id in reached.iter().filter(|id| **id != node.id) {
+            neighbors.extend(self.get_node(id)?);
+        }
+        neighbors.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut channels: Vec<NeuralChannel> = if radius == 0 {
+            Vec::new()
+        } else {
+            self.store.channels().filter(&mut |c| {
+                reached.contains(&c.from_node_id) && reached.contains(&c.to_node_id)
+            })?
+        };
+        channels.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Ok(Some(Neighborhood {
+            node,
+            neighbors,
+            channels,
+        }))
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{
+    core::{Cluster, Node},
+    engine::QueryResult,
+};
+
+/// Receives engine lifecycle events. Every method defaults to doing nothing,
+/// so observers implement only the events they care about. Callbacks run
+/// synchronously on the engine's task and should return quickly.
+pub trait EngineObserver: Send + Sync {
+    fn on_node_indexed(&self, _node: &Node) {}
+
+    fn on_cluster_formed(&self, _cluster: &Cluster) {}
+
+    fn on_query_executed(&self, _result: &QueryResult) {}
+
+    /// Fired after `on_query_executed` when dual-path validation rejected
+    /// the result.
+    fn on_validation_failed(&self, _result: &QueryResult) {}
+}
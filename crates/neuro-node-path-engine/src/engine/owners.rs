@@ -0,0 +1,68 @@
+use crate::{
+    engine::NeuroNodePathEngine,
+    repository::{blame, CodeRepository},
+};
+use std::path::Path;
+
+/// Key in `NodeMetadata::properties` holding a node's owners as a JSON
+/// array of author names.
+pub const OWNERS_PROPERTY: &str = "owners";
+
+impl NeuroNodePathEngine {
+    /// Records the top `max_owners` `git blame` authors of every indexed
+    /// file under `repository` in its node's `owners` property. Meant to run
+    /// after indexing a local git checkout; outside one it does nothing.
+    /// Files git does not track are skipped. Returns the number of nodes
+    /// that received owners.
+    pub fn record_owners(
+        &self,
+        repository: &CodeRepository,
+        max_owners: usize,
+    ) -> anyhow::Result<usize> {
+        let root = repository.get_root_path();
+        if !root.join(".git").exists() {
+            return Ok(0);
+        }
+
+        let files = self.store.nodes().filter(&mut |node| {
+            !node.source_path.is_empty() && Path::new(&node.source_path).starts_with(root)
+        })?;
+        let mut recorded = 0;
+        for node in files {
+            let owners = match blame::top_authors(root, &node.source_path, max_owners) {
+                Ok(owners) if !owners.is_empty() => owners,
+                Ok(_) => continue,
+                Err(e) => {
+                    tracing::debug!("No owners for {}: {}", node.source_path, e);
+                    continue;
+                }
+            };
+            self.store.nodes().update(&node.id, &mut |node| {
+                node.metadata
+                    .properties
+                    .insert(OWNERS_PROPERTY.to_string(), serde_json::json!(owners));
+            })?;
+            recorded += 1;
+        }
+        Ok(recorded)
+    }
+
+    /// Ids of nodes whose recorded owners include `owner`, sorted.
+    pub fn nodes_by_owner(&self, owner: &str) -> anyhow::Result<Vec<String>> {
+        let mut ids: Vec<String> = self
+            .store
+            .nodes()
+            .filter(&mut |node| {
+                node.metadata
+                    .properties
+                    .get(OWNERS_PROPERTY)
+                    .and_then(|owners| owners.as_array())
+                    .is_some_and(|owners| owners.iter().any(|o| o.as_str() == Some(owner)))
+            })?
+            .into_iter()
+            .map(|node| node.id)
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+}
@@ -1,43 +1,214 @@
-use crate::{core::Node, engine::QueryContext};
-use std::collections::VecDeque;
+use crate::{
+    core::Node,
+    engine::{name_index::NameIndex, QueryContext},
+    store::Table,
+    utils::normalize_name,
+    EngineError,
+};
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+use tokio_util::sync::CancellationToken;
 
 pub struct PathResolver {
     max_depth: usize,
+    normalize_names: bool,
+}
+
+/// Start node ids and the child ids under each parent, gathered in one
+/// pass over the nodes.
+struct Walk {
+    start_ids: Vec<String>,
+    children: HashMap<String, Vec<String>>,
+}
+
+/// A resolved path, possibly cut short.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedPath {
+    pub node_ids: Vec<String>,
+    /// Why resolution stopped before visiting every reachable node.
+    pub truncated: Option<String>,
 }
 
 impl PathResolver {
     pub fn new() -> Self {
-        Self { max_depth: 32 }
+        Self {
+            max_depth: 32,
+            normalize_names: false,
+        }
+    }
+
+    /// Follows child links at most `max_depth` levels below a start node.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Matches start nodes on normalized names: a node matches when its
+    /// normalized name and the normalized query contain one another as whole
+    /// words. A name index passed to this resolver must then be built over
+    /// normalized names padded with one space on each side.
+    pub fn with_normalized_names(mut self, normalize_names: bool) -> Self {
+        self.normalize_names = normalize_names;
+        self
+    }
+
+    /// Whether `node` starts a path for `query` when no name index is used:
+    /// its name appears in the query, or with normalized names, the
+    /// normalized forms contain one another as whole words.
+    pub fn matches_query(&self, query: &str, node: &Node) -> bool {
+        if self.normalize_names {
+            let query = format!(" {} ", normalize_name(query));
+            let name = format!(" {} ", node.normalized_name);
+            query.contains(&name) || name.contains(&query)
+        } else {
+            query.contains(&node.name)
+        }
     }
 
     pub fn resolve(&self, context: &QueryContext, nodes: &[Node]) -> anyhow::Result<Vec<String>> {
-        let mut path = Vec::new();
-        let mut queue = VecDeque::new();
+        self.resolve_cancellable(context, nodes, &CancellationToken::new())
+    }
+
+    /// Like [`Self::resolve`], but stops with [`EngineError::Cancelled`] once
+    /// `cancel` fires, checked before each frontier expansion.
+    pub fn resolve_cancellable(
+        &self,
+        context: &QueryContext,
+        nodes: &[Node],
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<Vec<String>> {
+        Ok(self.resolve_partial(context, nodes, None, cancel)?.node_ids)
+    }
 
-        let start_nodes: Vec<_> = nodes
-            .iter()
-            .filter(|n| context.query.contains(&n.name))
-            .collect();
+    /// Like [`Self::resolve_cancellable`], but selects start nodes through
+    /// `index`, which must cover the names of `nodes`. The result is the same.
+    pub fn resolve_indexed(
+        &self,
+        context: &QueryContext,
+        nodes: &[Node],
+        index: &NameIndex,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<Vec<String>> {
+        Ok(self.resolve_partial(context, nodes, Some(index), cancel)?.node_ids)
+    }
 
-        for node in start_nodes {
-            queue.push_back((node.id.clone(), 0));
+    /// Resolves through `index` when given, and stops early with the path
+    /// accumulated so far once the context's `deadline` passes.
+    pub fn resolve_partial(
+        &self,
+        context: &QueryContext,
+        nodes: &[Node],
+        index: Option<&NameIndex>,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<ResolvedPath> {
+        let walk = self.collect(context, index, |visit| {
+            nodes.iter().for_each(visit);
+            Ok(())
+        })?;
+        self.expand(walk, context.deadline, cancel)
+    }
+
+    /// Like [`Self::resolve_partial`], but reads the nodes passing `keep`
+    /// straight from `nodes` instead of a copy. Tables have no order of
+    /// their own, so start nodes and the children of each node are visited
+    /// in id order.
+    pub fn resolve_in(
+        &self,
+        context: &QueryContext,
+        nodes: &dyn Table<Node>,
+        keep: &dyn Fn(&Node) -> bool,
+        index: Option<&NameIndex>,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<ResolvedPath> {
+        let mut walk = self.collect(context, index, |visit| {
+            nodes.for_each(&mut |node| {
+                if keep(node) {
+                    visit(node);
+                }
+            })?;
+            Ok(())
+        })?;
+        walk.start_ids.sort();
+        for child_ids in walk.children.values_mut() {
+            child_ids.sort();
         }
+        self.expand(walk, context.deadline, cancel)
+    }
+
+    /// Visits every node once through `for_each_node`, collecting the ids of
+    /// the start nodes for `context` and the child ids under each parent, in
+    /// visiting order.
+    fn collect<F>(
+        &self,
+        context: &QueryContext,
+        index: Option<&NameIndex>,
+        for_each_node: F,
+    ) -> anyhow::Result<Walk>
+    where
+        F: FnOnce(&mut dyn FnMut(&Node)) -> anyhow::Result<()>,
+    {
+        let normalized_query = format!(" {} ", normalize_name(&context.query));
+        let matched = index.map(|index| match self.normalize_names {
+            true => index.matching_names(&normalized_query),
+            false => index.matching_names(&context.query),
+        });
+        let is_start = |node: &Node| match &matched {
+            Some(matched) if self.normalize_names => {
+                let name = format!(" {} ", node.normalized_name);
+                matched.contains(name.as_str()) || name.contains(&normalized_query)
+            }
+            Some(matched) => matched.contains(node.name.as_str()),
+            None => self.matches_query(&context.query, node),
+        };
+
+        let mut start_ids = Vec::new();
+        let mut children: HashMap<String, Vec<String>> = HashMap::new();
+        for_each_node(&mut |node| {
+            if is_start(node) {
+                start_ids.push(node.id.clone());
+            }
+            if let Some(parent_id) = &node.parent_id {
+                children.entry(parent_id.clone()).or_default().push(node.id.clone());
+            }
+        })?;
+        Ok(Walk { start_ids, children })
+    }
+
+    /// Breadth-first walk from the start nodes down the child lists.
+    fn expand(
+        &self,
+        walk: Walk,
+        deadline: Option<Instant>,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<ResolvedPath> {
+        let mut path = Vec::new();
+        let mut queue: VecDeque<(String, usize)> =
+            walk.start_ids.into_iter().map(|id| (id, 0)).collect();
 
         while let Some((node_id, depth)) = queue.pop_front() {
+            if cancel.is_cancelled() {
+                return Err(EngineError::Cancelled.into());
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Ok(ResolvedPath {
+                    node_ids: path,
+                    truncated: Some("deadline exceeded during path resolution".to_string()),
+                });
+            }
             if depth > self.max_depth {
                 break;
             }
 
-            path.push(node_id.clone());
-
-            for node in nodes {
-                if node.parent_id.as_ref() == Some(&node_id) {
-                    queue.push_back((node.id.clone(), depth + 1));
-                }
+            for child_id in walk.children.get(&node_id).into_iter().flatten() {
+                queue.push_back((child_id.clone(), depth + 1));
             }
+            path.push(node_id);
         }
 
-        Ok(path)
+        Ok(ResolvedPath {
+            node_ids: path,
+            truncated: None,
+        })
     }
 }
 
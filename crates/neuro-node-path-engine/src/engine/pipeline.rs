@@ -0,0 +1,342 @@
+use crate::{
+    core::Node,
+    engine::{
+        AuditTrail, ClusterNavigator, PathResolver, QueryContext, QueryProcessor, QueryResult,
+        NameIndex, StructuredQuery,
+    },
+    store::GraphStore,
+    validation::DualPathValidator,
+    EngineConfig,
+};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+/// Mutable state threaded through the query pipeline. Stages read the graph
+/// store, seeing only the nodes `keep` accepts, and fill in the result
+/// fields.
+pub struct QueryState<'a> {
+    pub context: QueryContext,
+    /// Which store nodes the query may visit.
+    pub keep: &'a dyn Fn(&Node) -> bool,
+    pub store: &'a dyn GraphStore,
+    pub audit_trail: &'a RwLock<AuditTrail>,
+    pub keywords: HashMap<String, Vec<String>>,
+    pub node_path: Vec<String>,
+    pub cluster_path: Vec<String>,
+    pub channel_interfaces: Vec<String>,
+    pub audit_path: Vec<String>,
+    pub explanation_en: String,
+    pub explanation_de: String,
+    /// The explanation in `context.language`, set by the explain stage.
+    pub explanation: String,
+    /// Set when the requested language could not be honoured in full.
+    pub language_note: Option<String>,
+    pub validation_status: String,
+    /// Fired when the caller abandons the query; long-running stages should
+    /// check it and return [`crate::EngineError::Cancelled`].
+    pub cancel: CancellationToken,
+    /// Set for [`crate::NeuroNodePathEngine::query_structured`]; `keep` then
+    /// accepts only the nodes passing its filters.
+    pub structured: Option<StructuredQuery>,
+    /// Index over node names used to pick start nodes, when available.
+    pub name_index: Option<Arc<NameIndex>>,
+    /// Set when path resolution stopped early; `node_path` is then partial.
+    pub partial_reason: Option<String>,
+}
+
+impl<'a> QueryState<'a> {
+    pub fn new(
+        context: QueryContext,
+        keep: &'a dyn Fn(&Node) -> bool,
+        store: &'a dyn GraphStore,
+        audit_trail: &'a RwLock<AuditTrail>,
+    ) -> Self {
+        Self {
+            context,
+            keep,
+            store,
+            audit_trail,
+            keywords: HashMap::new(),
+            node_path: Vec::new(),
+            cluster_path: Vec::new(),
+            channel_interfaces: Vec::new(),
+            audit_path: Vec::new(),
+            explanation_en: String::new(),
+            explanation_de: String::new(),
+            explanation: String::new(),
+            language_note: None,
+            validation_status: String::new(),
+            cancel: CancellationToken::new(),
+            structured: None,
+            name_index: None,
+            partial_reason: None,
+        }
+    }
+
+    /// Builds the result, applying the context's `offset` and `limit` to the
+    /// node path and interfaces.
+    pub fn into_result(self) -> QueryResult {
+        let total_nodes = self.node_path.len();
+        let total_interfaces = self.channel_interfaces.len();
+        let (offset, limit) = (self.context.offset, self.context.limit);
+        let page = |items: Vec<String>| -> Vec<String> {
+            items
+                .into_iter()
+                .skip(offset)
+                .take(limit.unwrap_or(usize::MAX))
+                .collect()
+        };
+
+        QueryResult {
+            request_id: self.context.request_id,
+            query: self.context.query,
+            node_path: page(self.node_path),
+            cluster_path: self.cluster_path,
+            channel_interfaces: page(self.channel_interfaces),
+            total_nodes,
+            total_interfaces,
+            audit_path: self.audit_path,
+            language: self.context.language,
+            explanation: self.explanation,
+            language_note: self.language_note,
+            explanation_en: self.explanation_en,
+            explanation_de: self.explanation_de,
+            validation_status: self.validation_status,
+            timestamp: chrono::Utc::now(),
+            partial: self.partial_reason.is_some(),
+            partial_reason: self.partial_reason,
+            structured: self.structured,
+        }
+    }
+}
+
+/// One step of query execution. The engine runs its stages in order and
+/// aborts the query on the first error.
+pub trait QueryStage: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()>;
+}
+
+/// The built-in stages in their default order:
+/// parse → resolve → navigate → channels → audit → explain → validate.
+pub fn default_pipeline(config: &EngineConfig) -> anyhow::Result<Vec<Box<dyn QueryStage>>> {
+    Ok(vec![
+        Box::new(ParseStage::new(
+            QueryProcessor::try_new()?.with_stop_words(config.stop_words.clone()),
+        )),
+        Box::new(ResolvePathStage::new(
+            PathResolver::new()
+                .with_max_depth(config.max_depth)
+                .with_normalized_names(config.normalize_names),
+        )),
+        Box::new(NavigateClustersStage::new(
+            ClusterNavigator::new().with_stop_words(config.stop_words.clone()),
+        )),
+        Box::new(ChannelStage),
+        Box::new(AuditStage),
+        Box::new(ExplainStage),
+        Box::new(ValidateStage::new(DualPathValidator::new(config.dual_path.clone()))),
+    ])
+}
+
+pub struct ParseStage {
+    processor: QueryProcessor,
+}
+
+impl ParseStage {
+    pub fn new(processor: QueryProcessor) -> Self {
+        Self { processor }
+    }
+}
+
+impl QueryStage for ParseStage {
+    fn name(&self) -> &str {
+        "parse"
+    }
+
+    fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()> {
+        state.keywords = self.processor.parse_query(&state.context);
+        Ok(())
+    }
+}
+
+pub struct ResolvePathStage {
+    resolver: PathResolver,
+}
+
+impl ResolvePathStage {
+    pub fn new(resolver: PathResolver) -> Self {
+        Self { resolver }
+    }
+}
+
+impl ResolvePathStage {
+    fn resolve(&self, context: &QueryContext, state: &mut QueryState<'_>) -> anyhow::Result<Vec<String>> {
+        let resolved = self.resolver.resolve_in(
+            context,
+            state.store.nodes(),
+            state.keep,
+            state.name_index.as_deref(),
+            &state.cancel,
+        )?;
+        state.partial_reason = resolved.truncated;
+        Ok(resolved.node_ids)
+    }
+}
+
+impl QueryStage for ResolvePathStage {
+    fn name(&self) -> &str {
+        "resolve"
+    }
+
+    fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()> {
+        state.node_path = match &state.structured {
+            Some(structured) if structured.free_text.is_empty() => {
+                let mut matched = Vec::new();
+                state.store.nodes().for_each(&mut |n| {
+                    if (state.keep)(n) {
+                        matched.push((n.source_path.clone(), n.id.clone()));
+                    }
+                })?;
+                matched.sort();
+                matched.into_iter().map(|(_, id)| id).collect()
+            }
+            Some(structured) => {
+                let context = QueryContext {
+                    query: structured.free_text.clone(),
+                    ..state.context.clone()
+                };
+                self.resolve(&context, state)?
+            }
+            None => {
+                let context = state.context.clone();
+                self.resolve(&context, state)?
+            }
+        };
+        Ok(())
+    }
+}
+
+pub struct NavigateClustersStage {
+    navigator: ClusterNavigator,
+}
+
+impl NavigateClustersStage {
+    pub fn new(navigator: ClusterNavigator) -> Self {
+        Self { navigator }
+    }
+}
+
+impl QueryStage for NavigateClustersStage {
+    fn name(&self) -> &str {
+        "navigate"
+    }
+
+    fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()> {
+        state.cluster_path = self.navigator.navigate(&state.context, state.store.clusters())?;
+        Ok(())
+    }
+}
+
+/// Collects the interfaces declared by nodes on the resolved path.
+pub struct ChannelStage;
+
+impl QueryStage for ChannelStage {
+    fn name(&self) -> &str {
+        "channels"
+    }
+
+    fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()> {
+        let mut interfaces: Vec<(usize, String, String)> = Vec::new();
+        state.store.interfaces().for_each(&mut |i| {
+            let position = i
+                .source_node_id
+                .as_ref()
+                .and_then(|source| state.node_path.iter().position(|id| id == source));
+            if let Some(position) = position {
+                interfaces.push((position, i.name.clone(), i.id.clone()));
+            }
+        })?;
+        interfaces.sort();
+        state.channel_interfaces = interfaces.into_iter().map(|(_, _, id)| id).collect();
+        Ok(())
+    }
+}
+
+pub struct AuditStage;
+
+impl QueryStage for AuditStage {
+    fn name(&self) -> &str {
+        "audit"
+    }
+
+    fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()> {
+        let mut audit_trail = state.audit_trail.write();
+        state.audit_path = audit_trail.log_partial_query(
+            &state.context,
+            &state.node_path,
+            &state.cluster_path,
+            state.partial_reason.as_deref(),
+        )?;
+        Ok(())
+    }
+}
+
+pub struct ExplainStage;
+
+impl QueryStage for ExplainStage {
+    fn name(&self) -> &str {
+        "explain"
+    }
+
+    fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()> {
+        state.explanation_en = "Query execution completed through neural node paths with cluster traversal and channel validation.".to_string();
+        state.explanation_de = "Abfrageausführung über Neuroknotenpfade mit Clusterdurchquerung und Kanalvalidierung abgeschlossen.".to_string();
+        state.explanation = match state.context.language.as_str() {
+            "de" => state.explanation_de.clone(),
+            "en" => state.explanation_en.clone(),
+            other => {
+                state.language_note.get_or_insert_with(|| {
+                    format!("No explanation available in '{}'; answered in English", other)
+                });
+                state.explanation_en.clone()
+            }
+        };
+        Ok(())
+    }
+}
+
+pub struct ValidateStage {
+    validator: DualPathValidator,
+}
+
+impl ValidateStage {
+    pub fn new(validator: DualPathValidator) -> Self {
+        Self { validator }
+    }
+}
+
+impl QueryStage for ValidateStage {
+    fn name(&self) -> &str {
+        "validate"
+    }
+
+    fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()> {
+        let mut cluster_members: Vec<String> = Vec::new();
+        for cluster_id in &state.cluster_path {
+            if let Some(cluster) = state.store.clusters().get(cluster_id)? {
+                cluster_members.extend(cluster.node_ids);
+            }
+        }
+
+        state.validation_status = self.validator.validate_with_members(
+            &state.node_path,
+            &state.cluster_path,
+            &cluster_members,
+        )?;
+        Ok(())
+    }
+}
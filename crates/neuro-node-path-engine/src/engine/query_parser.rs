@@ -0,0 +1,107 @@
+use crate::core::{node::NodeType, Node};
+use serde::{Deserialize, Serialize};
+
+const FIELDS: &str = "type:, lang:, cluster:, repo:, tag:, name~";
+
+/// A query split into field filters and the remaining free text.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StructuredQuery {
+    pub type_filter: Option<NodeType>,
+    pub lang_filter: Option<String>,
+    pub cluster_filter: Option<String>,
+    /// Repository namespace the node must belong to.
+    pub repo_filter: Option<String>,
+    /// Tags the node must all carry; `tag:` may be given more than once.
+    pub tag_filter: Vec<String>,
+    /// Substring the node name must contain.
+    pub name_match: Option<String>,
+    pub free_text: String,
+}
+
+impl StructuredQuery {
+    /// Checks the type, language, repository, tag and name filters. Cluster membership needs
+    /// the cluster graph and is checked by the engine.
+    pub fn matches(&self, node: &Node) -> bool {
+        self.type_filter.as_ref().is_none_or(|t| *t == node.node_type)
+            && self
+                .lang_filter
+                .as_ref()
+                .is_none_or(|l| node.metadata.language.eq_ignore_ascii_case(l))
+            && self
+                .repo_filter
+                .as_ref()
+                .is_none_or(|r| node.repo_id.as_ref() == Some(r))
+            && self.tag_filter.iter().all(|t| node.metadata.tags.contains(t))
+            && self
+                .name_match
+                .as_ref()
+                .is_none_or(|n| node.name.contains(n.as_str()))
+    }
+}
+
+/// Parses queries such as `type:function lang:rust cluster:service repo:api tag:hotpath name~user`.
+/// Unprefixed terms are kept as free text.
+pub struct QueryParser;
+
+impl QueryParser {
+    pub fn parse(input: &str) -> anyhow::Result<StructuredQuery> {
+        let mut query = StructuredQuery::default();
+        let mut free_text = Vec::new();
+
+        for term in input.split_whitespace() {
+            if let Some((field, value)) = term.split_once('~') {
+                match field {
+                    "name" => query.name_match = Some(non_empty(field, value)?),
+                    _ => anyhow::bail!(
+                        "Unknown query field '{}~'; only name~ supports substring matching (fields: {})",
+                        field,
+                        FIELDS
+                    ),
+                }
+            } else if let Some((field, value)) = term.split_once(':') {
+                let value = non_empty(field, value)?;
+                match field {
+                    "type" => query.type_filter = Some(parse_node_type(&value)),
+                    "lang" => query.lang_filter = Some(value),
+                    "cluster" => query.cluster_filter = Some(value),
+                    "repo" => query.repo_filter = Some(value),
+                    "tag" => query.tag_filter.push(value),
+                    _ => anyhow::bail!(
+                        "Unknown query field '{}:'; expected one of {}",
+                        field,
+                        FIELDS
+                    ),
+                }
+            } else {
+                free_text.push(term);
+            }
+        }
+
+        query.free_text = free_text.join(" ");
+        Ok(query)
+    }
+}
+
+fn non_empty(field: &str, value: &str) -> anyhow::Result<String> {
+    if value.is_empty() {
+        anyhow::bail!("Query field '{}' needs a value", field);
+    }
+    Ok(value.to_string())
+}
+
+/// Maps the lowercase names used by `NodeType`'s `Display` back to variants;
+/// anything else is a custom type.
+fn parse_node_type(value: &str) -> NodeType {
+    match value.to_lowercase().as_str() {
+        "repository" => NodeType::Repository,
+        "file" => NodeType::File,
+        "function" => NodeType::Function,
+        "struct" => NodeType::Struct,
+        "module" => NodeType::Module,
+        "protocol" => NodeType::Protocol,
+        "model" => NodeType::Model,
+        "interface" => NodeType::Interface,
+        "config" => NodeType::Config,
+        _ => NodeType::Custom(value.to_string()),
+    }
+}
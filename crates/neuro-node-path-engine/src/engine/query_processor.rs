@@ -1,35 +1,52 @@
-use crate::engine::QueryContext;
+use crate::{engine::QueryContext, i18n::StopWords};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
 
+const QUERY_PATTERNS: &[(&str, &str)] = &[
+    (r"(?i)(neural|neuro)", "neural_keyword"),
+    (r"(?i)(cluster|clust)", "cluster_keyword"),
+    (r"(?i)(path|route)", "path_keyword"),
+    (r"(?i)(channel|interface)", "channel_keyword"),
+];
+
+static COMPILED_QUERY_PATTERNS: Lazy<Result<Vec<(Regex, String)>, regex::Error>> =
+    Lazy::new(|| {
+        QUERY_PATTERNS
+            .iter()
+            .map(|(pattern, key)| Ok((Regex::new(pattern)?, key.to_string())))
+            .collect()
+    });
+
 pub struct QueryProcessor {
     query_patterns: Vec<(Regex, String)>,
+    stop_words: StopWords,
 }
 
 impl QueryProcessor {
+    /// Builds a processor from the shared, lazily compiled pattern set.
+    ///
+    /// Panics if a built-in pattern fails to compile; use [`QueryProcessor::try_new`]
+    /// where a startup failure must be reported instead.
     pub fn new() -> Self {
-        let patterns = vec![
-            (
-                Regex::new(r"(?i)(neural|neuro)").unwrap(),
-                "neural_keyword".to_string(),
-            ),
-            (
-                Regex::new(r"(?i)(cluster|clust)").unwrap(),
-                "cluster_keyword".to_string(),
-            ),
-            (
-                Regex::new(r"(?i)(path|route)").unwrap(),
-                "path_keyword".to_string(),
-            ),
-            (
-                Regex::new(r"(?i)(channel|interface)").unwrap(),
-                "channel_keyword".to_string(),
-            ),
-        ];
-
-        Self {
-            query_patterns: patterns,
-        }
+        Self::try_new().expect("built-in query patterns must compile")
+    }
+
+    pub fn try_new() -> anyhow::Result<Self> {
+        let patterns = COMPILED_QUERY_PATTERNS
+            .as_ref()
+            .map_err(|e| anyhow::anyhow!("Invalid query pattern: {}", e))?;
+
+        Ok(Self {
+            query_patterns: patterns.clone(),
+            stop_words: StopWords::default(),
+        })
+    }
+
+    /// Replaces the built-in English and German stop words.
+    pub fn with_stop_words(mut self, stop_words: StopWords) -> Self {
+        self.stop_words = stop_words;
+        self
     }
 
     pub fn parse_query(&self, context: &QueryContext) -> HashMap<String, Vec<String>> {
@@ -46,11 +63,18 @@ impl QueryProcessor {
         tokens
     }
 
+    /// Lowercased words of an English `query` longer than three characters,
+    /// without stop words.
     pub fn extract_entities(&self, query: &str) -> Vec<String> {
+        self.extract_entities_in(query, "en")
+    }
+
+    /// [`Self::extract_entities`] for a query in `language`.
+    pub fn extract_entities_in(&self, query: &str, language: &str) -> Vec<String> {
         query
             .split_whitespace()
             .map(|s| s.to_lowercase())
-            .filter(|s| s.len() > 3)
+            .filter(|s| s.chars().count() > 3 && !self.stop_words.contains(language, s))
             .collect()
     }
 }
@@ -0,0 +1,91 @@
+use crate::{
+    engine::{IndexReport, NeuroNodePathEngine},
+    repository::CodeRepository,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tokio_util::sync::CancellationToken;
+
+impl NeuroNodePathEngine {
+    /// Indexes `repository` under the `repo_id` namespace so several
+    /// repositories can share one graph. Nodes and clusters are tagged with
+    /// `repo_id`, and a changed repository replaces what was previously
+    /// indexed under the same namespace. Filter queries with `repo:<id>` via
+    /// [`Self::query_structured`].
+    pub async fn index_repository_as(
+        &mut self,
+        repo_id: &str,
+        repository: &CodeRepository,
+    ) -> anyhow::Result<IndexReport> {
+        self.index_into(Some(repo_id), repository, |_| {}, &CancellationToken::new())
+            .await
+    }
+
+    /// Removes every node and cluster in the `repo_id` namespace, together
+    /// with channels touching those nodes and interfaces they declared.
+    /// Returns the number of nodes removed.
+    pub fn remove_repository(&mut self, repo_id: &str) -> anyhow::Result<usize> {
+        self.clear_namespace(Some(repo_id))
+    }
+
+    /// [`Self::remove_repository`] for any namespace, `None` being the
+    /// default one. Also forgets the namespace's last index run, so the
+    /// next run indexes it again.
+    pub(crate) fn clear_namespace(&mut self, namespace: Option<&str>) -> anyhow::Result<usize> {
+        let removed: HashSet<String> = self
+            .store
+            .nodes()
+            .filter(&mut |n| n.repo_id.as_deref() == namespace)?
+            .into_iter()
+            .map(|n| n.id)
+            .collect();
+
+        // Forget the index state first, so a store failure below leaves the
+        // namespace to be indexed again rather than trusted.
+        self.last_index.remove(&namespace.map(str::to_string));
+        *self.name_index.write() = None;
+        self.store.nodes().retain(&mut |n| !removed.contains(&n.id))?;
+        self.store
+            .clusters()
+            .retain(&mut |c| c.repo_id.as_deref() != namespace)?;
+        self.store.channels().retain(&mut |c| {
+            !removed.contains(&c.from_node_id) && !removed.contains(&c.to_node_id)
+        })?;
+        self.store.interfaces().retain(&mut |i| {
+            i.source_node_id
+                .as_ref()
+                .is_none_or(|source| !removed.contains(source))
+        })?;
+        Ok(removed.len())
+    }
+
+    /// Namespaces that currently have nodes, sorted.
+    pub fn repositories(&self) -> anyhow::Result<Vec<String>> {
+        let mut namespaces = BTreeSet::new();
+        self.store.nodes().for_each(&mut |n| {
+            if let Some(repo_id) = &n.repo_id {
+                namespaces.insert(repo_id.clone());
+            }
+        })?;
+        Ok(namespaces.into_iter().collect())
+    }
+
+    /// Channels linking nodes of two different namespaces, e.g. a service
+    /// calling into a shared library indexed separately. Create them with
+    /// [`Self::add_channel`] like any other channel.
+    pub fn cross_repo_channels(&self) -> anyhow::Result<Vec<String>> {
+        let mut repo_of: HashMap<String, Option<String>> = HashMap::new();
+        self.store.nodes().for_each(&mut |n| {
+            repo_of.insert(n.id.clone(), n.repo_id.clone());
+        })?;
+        let repo_of = |id: &str| repo_of.get(id).cloned().flatten();
+        let mut channels: Vec<String> = self
+            .store
+            .channels()
+            .filter(&mut |c| repo_of(&c.from_node_id) != repo_of(&c.to_node_id))?
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        channels.sort();
+        Ok(channels)
+    }
+}
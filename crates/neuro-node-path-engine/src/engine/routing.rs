@@ -0,0 +1,110 @@
+use crate::engine::NeuroNodePathEngine;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Cheapest route between two nodes found by [`NeuroNodePathEngine::weighted_path`].
+#[derive(Debug, Clone, Serialize)]
+pub struct WeightedPath {
+    pub node_ids: Vec<String>,
+    pub channel_ids: Vec<String>,
+    pub cost: f64,
+}
+
+#[derive(PartialEq)]
+struct Frontier {
+    cost: f64,
+    node_id: String,
+}
+
+impl Eq for Frontier {}
+
+impl Ord for Frontier {
+    // Reversed so the max-heap pops the cheapest node; ties break by id.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.node_id.cmp(&self.node_id))
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl NeuroNodePathEngine {
+    /// Dijkstra over active channels from `from` to `to`. A channel's
+    /// strength is its weight times the configured weight of its signal
+    /// type, and crossing it costs the inverse, so strongly weighted
+    /// relationships dominate. Channels with no positive strength are skipped.
+    pub fn weighted_path(&self, from: &str, to: &str) -> anyhow::Result<Option<WeightedPath>> {
+        let mut adjacency: HashMap<String, Vec<(String, String, f64)>> = HashMap::new();
+        for channel in self.store.channels().filter(&mut |c| c.active)? {
+            let strength = channel.weight * self.config.signal_weight(&channel.signal_type);
+            if strength > 0.0 {
+                adjacency.entry(channel.from_node_id.clone()).or_default().push((
+                    channel.id.clone(),
+                    channel.to_node_id.clone(),
+                    1.0 / strength,
+                ));
+            }
+        }
+
+        let mut best: HashMap<String, f64> = HashMap::from([(from.to_string(), 0.0)]);
+        let mut came_from: HashMap<String, (String, String)> = HashMap::new();
+        let mut heap = BinaryHeap::from([Frontier {
+            cost: 0.0,
+            node_id: from.to_string(),
+        }]);
+
+        while let Some(Frontier { cost, node_id }) = heap.pop() {
+            if node_id == to {
+                return Ok(Some(reconstruct(from, to, &came_from, cost)));
+            }
+            if best.get(&node_id).is_some_and(|b| cost > *b) {
+                continue;
+            }
+            for (channel_id, next, step) in adjacency.get(&node_id).into_iter().flatten() {
+                let next_cost = cost + step;
+                if best.get(next).is_none_or(|b| next_cost < *b) {
+                    best.insert(next.clone(), next_cost);
+                    came_from.insert(next.clone(), (node_id.clone(), channel_id.clone()));
+                    heap.push(Frontier {
+                        cost: next_cost,
+                        node_id: next.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn reconstruct(
+    from: &str,
+    to: &str,
+    came_from: &HashMap<String, (String, String)>,
+    cost: f64,
+) -> WeightedPath {
+    let mut node_ids = vec![to.to_string()];
+    let mut channel_ids = Vec::new();
+    let mut current = to;
+    while current != from {
+        let (previous, channel_id) = &came_from[current];
+        node_ids.push(previous.clone());
+        channel_ids.push(channel_id.clone());
+        current = previous;
+    }
+    node_ids.reverse();
+    channel_ids.reverse();
+
+    WeightedPath {
+        node_ids,
+        channel_ids,
+        cost,
+    }
+}
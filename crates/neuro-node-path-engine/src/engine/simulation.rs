@@ -0,0 +1,103 @@
+use crate::{engine::NeuroNodePathEngine, EngineError};
+use std::collections::{HashMap, HashSet};
+
+impl NeuroNodePathEngine {
+    /// Advances the neuron network by one step.
+    ///
+    /// Neurons named in `inputs` fire with their input signal, then every
+    /// active channel leaving a fired neuron transmits its output one hop.
+    /// Downstream neurons fire once with the sum of their incoming signals.
+    /// Each neuron fires at most once per tick. The returned map holds every
+    /// neuron's output after the tick, including neurons that did not fire.
+    pub fn simulate_tick(
+        &self,
+        inputs: HashMap<String, f64>,
+    ) -> anyhow::Result<HashMap<String, f64>> {
+        let channels = self.store.channels().values()?;
+        let mut fired: HashMap<String, f64> = HashMap::new();
+
+        for (neuron_id, signal) in &inputs {
+            match self.neurons.get_mut(neuron_id) {
+                Some(mut neuron) => {
+                    fired.insert(neuron_id.clone(), neuron.fire(*signal));
+                }
+                None => tracing::warn!("Simulation input for unknown neuron: {}", neuron_id),
+            }
+        }
+
+        let mut incoming: HashMap<String, f64> = HashMap::new();
+        for channel in channels {
+            let Some(output) = fired.get(&channel.from_node_id) else {
+                continue;
+            };
+            self.store.channels().update(&channel.id, &mut |channel| {
+                let transmitted = channel.transmit_signal(*output);
+                if channel.active {
+                    *incoming.entry(channel.to_node_id.clone()).or_insert(0.0) += transmitted;
+                }
+            })?;
+        }
+
+        let sources: HashSet<String> = fired.keys().cloned().collect();
+        for (neuron_id, signal) in incoming {
+            if sources.contains(&neuron_id) {
+                continue;
+            }
+            if let Some(mut neuron) = self.neurons.get_mut(&neuron_id) {
+                fired.insert(neuron_id, neuron.fire(signal));
+            }
+        }
+
+        Ok(self
+            .neurons
+            .iter()
+            .map(|neuron| {
+                let output = fired
+                    .get(neuron.key())
+                    .copied()
+                    .unwrap_or_else(|| neuron.output());
+                (neuron.key().clone(), output)
+            })
+            .collect())
+    }
+
+    /// Pushes `initial` along `path`, a sequence of node ids, through the
+    /// channel leading from each node to the next (the lowest channel id
+    /// when there are several). Returns each channel used with the signal
+    /// after that hop, so the last value is the end-to-end signal.
+    ///
+    /// Fails with [`EngineError::MissingChannel`] at the first pair of
+    /// consecutive nodes without a channel; no signal is transmitted then.
+    pub fn transmit_along(
+        &self,
+        path: &[String],
+        initial: f64,
+    ) -> anyhow::Result<Vec<(String, f64)>> {
+        let mut hops = Vec::with_capacity(path.len().saturating_sub(1));
+        for pair in path.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            let channel_id = self
+                .store
+                .channels()
+                .filter(&mut |c| &c.from_node_id == from && &c.to_node_id == to)?
+                .into_iter()
+                .map(|c| c.id)
+                .min()
+                .ok_or_else(|| EngineError::MissingChannel {
+                    from: from.clone(),
+                    to: to.clone(),
+                })?;
+            hops.push(channel_id);
+        }
+
+        let mut signal = initial;
+        let mut transmitted = Vec::with_capacity(hops.len());
+        for channel_id in hops {
+            self.store.channels().update(&channel_id, &mut |channel| {
+                signal = channel.transmit_signal(signal);
+            })?;
+            transmitted.push((channel_id, signal));
+        }
+        Ok(transmitted)
+    }
+}
@@ -0,0 +1,102 @@
+use crate::engine::NeuroNodePathEngine;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Fan-in and fan-out of one node over all channels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Degree {
+    pub fan_in: usize,
+    pub fan_out: usize,
+}
+
+impl Degree {
+    pub fn total(&self) -> usize {
+        self.fan_in + self.fan_out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum SmellKind {
+    /// A node most of the graph depends on or talks to, such as a singleton
+    /// or manager that has absorbed too many responsibilities.
+    GodObject,
+}
+
+/// A structural hotspot found by [`NeuroNodePathEngine::god_objects`].
+#[derive(Debug, Clone, Serialize)]
+pub struct StructuralSmell {
+    pub node_id: String,
+    pub kind: SmellKind,
+    pub degree: Degree,
+    /// Standard deviations the node's total degree lies above the mean.
+    pub z_score: f64,
+    /// The z-score the node had to exceed to be reported.
+    pub threshold: f64,
+}
+
+impl NeuroNodePathEngine {
+    /// Degree centrality of every node: channels arriving at it (fan-in) and
+    /// leaving it (fan-out). Channels to unknown nodes are ignored.
+    pub fn degree_centrality(&self) -> anyhow::Result<HashMap<String, Degree>> {
+        let mut degrees: HashMap<String, Degree> = HashMap::new();
+        self.store.nodes().for_each(&mut |n| {
+            degrees.insert(n.id.clone(), Degree::default());
+        })?;
+        self.store.channels().for_each(&mut |channel| {
+            if !degrees.contains_key(&channel.from_node_id)
+                || !degrees.contains_key(&channel.to_node_id)
+            {
+                return;
+            }
+            if let Some(degree) = degrees.get_mut(&channel.from_node_id) {
+                degree.fan_out += 1;
+            }
+            if let Some(degree) = degrees.get_mut(&channel.to_node_id) {
+                degree.fan_in += 1;
+            }
+        })?;
+        Ok(degrees)
+    }
+
+    /// Nodes whose total degree lies more than `z_threshold` standard
+    /// deviations above the mean over all nodes, highest degree first.
+    /// A graph where every node has the same degree has none.
+    pub fn god_objects(&self, z_threshold: f64) -> anyhow::Result<Vec<StructuralSmell>> {
+        let degrees = self.degree_centrality()?;
+        if degrees.is_empty() {
+            return Ok(Vec::new());
+        }
+        let count = degrees.len() as f64;
+        let mean = degrees.values().map(|d| d.total() as f64).sum::<f64>() / count;
+        let variance = degrees
+            .values()
+            .map(|d| (d.total() as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let deviation = variance.sqrt();
+        if deviation == 0.0 {
+            return Ok(Vec::new());
+        }
+
+        let mut smells: Vec<StructuralSmell> = degrees
+            .into_iter()
+            .filter_map(|(node_id, degree)| {
+                let z_score = (degree.total() as f64 - mean) / deviation;
+                (z_score > z_threshold).then_some(StructuralSmell {
+                    node_id,
+                    kind: SmellKind::GodObject,
+                    degree,
+                    z_score,
+                    threshold: z_threshold,
+                })
+            })
+            .collect();
+        smells.sort_by(|a, b| {
+            b.degree
+                .total()
+                .cmp(&a.degree.total())
+                .then_with(|| a.node_id.cmp(&b.node_id))
+        });
+        Ok(smells)
+    }
+}
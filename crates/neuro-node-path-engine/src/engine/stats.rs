@@ -0,0 +1,102 @@
+use crate::engine::NeuroNodePathEngine;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Digest of the indexed graph from [`NeuroNodePathEngine::repository_stats`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RepositoryStats {
+    pub node_count: usize,
+    /// Keyed by the node type's display name, e.g. `file` or `config`.
+    pub nodes_by_type: BTreeMap<String, usize>,
+    pub channel_count: usize,
+    /// Keyed by the signal type's display name, e.g. `data_flow`.
+    pub channels_by_signal: BTreeMap<String, usize>,
+    pub cluster_count: usize,
+    /// Mean `cohesion_score` over all clusters; 0 without clusters.
+    pub average_cohesion: f64,
+    /// Nodes with no channel and no parent or child.
+    pub orphan_count: usize,
+    /// Longest chain of `parent_id` links from any node up to a root.
+    pub max_depth: usize,
+}
+
+impl NeuroNodePathEngine {
+    /// Counts over the whole graph. Linear in its size, so cheap enough to
+    /// call after every reindex.
+    pub fn repository_stats(&self) -> anyhow::Result<RepositoryStats> {
+        let mut stats = RepositoryStats::default();
+        let mut parents: HashMap<String, String> = HashMap::new();
+        let mut linked: HashSet<String> = HashSet::new();
+
+        self.store.nodes().for_each(&mut |node| {
+            stats.node_count += 1;
+            *stats.nodes_by_type.entry(node.node_type.to_string()).or_default() += 1;
+            if let Some(parent_id) = &node.parent_id {
+                parents.insert(node.id.clone(), parent_id.clone());
+                linked.insert(node.id.clone());
+                linked.insert(parent_id.clone());
+            }
+            if !node.children.is_empty() {
+                linked.insert(node.id.clone());
+            }
+        })?;
+
+        self.store.channels().for_each(&mut |channel| {
+            stats.channel_count += 1;
+            *stats
+                .channels_by_signal
+                .entry(channel.signal_type.to_string())
+                .or_default() += 1;
+            linked.insert(channel.from_node_id.clone());
+            linked.insert(channel.to_node_id.clone());
+        })?;
+
+        let mut cohesion_sum = 0.0;
+        self.store.clusters().for_each(&mut |cluster| {
+            stats.cluster_count += 1;
+            cohesion_sum += cluster.cohesion_score;
+        })?;
+        if stats.cluster_count > 0 {
+            stats.average_cohesion = cohesion_sum / stats.cluster_count as f64;
+        }
+
+        self.store.nodes().for_each(&mut |n| {
+            if !linked.contains(&n.id) {
+                stats.orphan_count += 1;
+            }
+        })?;
+        stats.max_depth = max_parent_depth(&parents);
+        Ok(stats)
+    }
+}
+
+/// Depth of the deepest node, memoised per node. A `parent_id` cycle ends
+/// the chain where it would revisit a node.
+fn max_parent_depth(parents: &HashMap<String, String>) -> usize {
+    let mut depths: HashMap<&str, usize> = HashMap::new();
+    let mut deepest = 0;
+    for start in parents.keys() {
+        let mut chain: Vec<&str> = Vec::new();
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut current = start.as_str();
+        let base = loop {
+            if let Some(depth) = depths.get(current) {
+                break depth + 1;
+            }
+            if !seen.insert(current) {
+                break 0;
+            }
+            chain.push(current);
+            match parents.get(current) {
+                Some(parent) => current = parent.as_str(),
+                None => break 0,
+            }
+        };
+        for (offset, id) in chain.iter().rev().enumerate() {
+            let depth = base + offset;
+            depths.insert(id, depth);
+            deepest = deepest.max(depth);
+        }
+    }
+    deepest
+}
@@ -0,0 +1,34 @@
+use crate::engine::NeuroNodePathEngine;
+
+impl NeuroNodePathEngine {
+    /// Adds `tag` to a node. Tags are stored in `NodeMetadata::tags` and so
+    /// serialize with the node. Returns `false` if the node does not exist.
+    pub fn tag_node(&self, node_id: &str, tag: &str) -> anyhow::Result<bool> {
+        Ok(self.store.nodes().update(node_id, &mut |node| {
+            node.metadata.tags.insert(tag.to_string());
+        })?)
+    }
+
+    /// Removes `tag` from a node. Returns `false` if the node does not exist
+    /// or did not carry the tag.
+    pub fn untag_node(&self, node_id: &str, tag: &str) -> anyhow::Result<bool> {
+        let mut removed = false;
+        self.store.nodes().update(node_id, &mut |node| {
+            removed = node.metadata.tags.remove(tag);
+        })?;
+        Ok(removed)
+    }
+
+    /// Ids of all nodes carrying `tag`, sorted.
+    pub fn nodes_with_tag(&self, tag: &str) -> anyhow::Result<Vec<String>> {
+        let mut ids: Vec<String> = self
+            .store
+            .nodes()
+            .filter(&mut |n| n.metadata.tags.contains(tag))?
+            .into_iter()
+            .map(|n| n.id)
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+}
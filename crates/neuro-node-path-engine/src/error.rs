@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+/// Errors callers may want to match on. They are returned inside
+/// `anyhow::Error`; use `downcast_ref::<EngineError>()` to inspect them.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum EngineError {
+    #[error("operation cancelled")]
+    Cancelled,
+    /// Indexing would grow the graph past [`crate::EngineConfig::max_nodes`]
+    /// or [`crate::EngineConfig::max_channels`]; `kind` says which.
+    #[error("graph too large: {actual} {kind} exceed the limit of {limit}")]
+    GraphTooLarge {
+        kind: &'static str,
+        limit: usize,
+        actual: usize,
+    },
+    /// Two consecutive nodes of a path have no channel between them.
+    #[error("no channel from {from} to {to}")]
+    MissingChannel { from: String, to: String },
+    /// A model file is larger than the caller's byte limit.
+    #[error("model file is {actual} bytes, over the limit of {limit}")]
+    ModelTooLarge { limit: u64, actual: u64 },
+    /// A JSON model nests arrays or objects deeper than the loader allows.
+    #[error("model JSON nests deeper than {limit} levels")]
+    ModelTooDeep { limit: usize },
+    /// The [`crate::store::GraphStore`] backend failed to read or write.
+    #[error("graph store failed: {message}")]
+    Store { message: String },
+}
@@ -0,0 +1,179 @@
+use crate::i18n::lemma_store::{KnotenlexikonStore, LemmaEntry};
+
+const CSV_HEADER: &str = "canonical_id,german_label,english_label,german_definition,english_definition,pronunciation_de,ipa_de,word_type,related_concepts";
+
+impl KnotenlexikonStore {
+    /// Exports the lexicon as a TBX-Basic termbase, one `conceptEntry` per lemma
+    /// with a German and an English language section. The German term carries
+    /// the respelling and, when set, the IPA between slashes as pronunciation
+    /// notes.
+    pub fn export_tbx(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<tbx type=\"TBX-Basic\" style=\"dca\" xml:lang=\"en\" xmlns=\"urn:iso:std:iso:30042:ed-2\">\n");
+        out.push_str("  <tbxHeader>\n");
+        out.push_str("    <fileDesc>\n");
+        out.push_str("      <sourceDesc><p>Knotenlexikon</p></sourceDesc>\n");
+        out.push_str("    </fileDesc>\n");
+        out.push_str("  </tbxHeader>\n");
+        out.push_str("  <text>\n");
+        out.push_str("    <body>\n");
+
+        for entry in sorted_entries(self) {
+            out.push_str(&format!(
+                "      <conceptEntry id=\"{}\">\n",
+                escape_xml(&entry.canonical_id)
+            ));
+            for related in &entry.related_concepts {
+                out.push_str(&format!(
+                    "        <ref type=\"crossReference\" target=\"{}\">{}</ref>\n",
+                    escape_xml(related),
+                    escape_xml(related)
+                ));
+            }
+
+            out.push_str("        <langSec xml:lang=\"de\">\n");
+            out.push_str(&format!(
+                "          <descrip type=\"definition\">{}</descrip>\n",
+                escape_xml(&entry.german_definition)
+            ));
+            out.push_str("          <termSec>\n");
+            out.push_str(&format!(
+                "            <term>{}</term>\n",
+                escape_xml(&entry.german_label)
+            ));
+            out.push_str(&format!(
+                "            <termNote type=\"pronunciation\">{}</termNote>\n",
+                escape_xml(&entry.pronunciation_de)
+            ));
+            if let Some(ipa) = &entry.ipa_de {
+                out.push_str(&format!(
+                    "            <termNote type=\"pronunciation\">/{}/</termNote>\n",
+                    escape_xml(ipa)
+                ));
+            }
+            out.push_str(&format!(
+                "            <note>{}</note>\n",
+                escape_xml(&entry.word_type)
+            ));
+            out.push_str("          </termSec>\n");
+            out.push_str("        </langSec>\n");
+
+            out.push_str("        <langSec xml:lang=\"en\">\n");
+            out.push_str(&format!(
+                "          <descrip type=\"definition\">{}</descrip>\n",
+                escape_xml(&entry.english_definition)
+            ));
+            out.push_str("          <termSec>\n");
+            out.push_str(&format!(
+                "            <term>{}</term>\n",
+                escape_xml(&entry.english_label)
+            ));
+            out.push_str("          </termSec>\n");
+            out.push_str("        </langSec>\n");
+            out.push_str("      </conceptEntry>\n");
+        }
+
+        out.push_str("    </body>\n");
+        out.push_str("  </text>\n");
+        out.push_str("</tbx>\n");
+        out
+    }
+
+    /// Exports the lexicon as RFC 4180 CSV with a header row. Related concepts
+    /// are joined with `;` inside a single column; `ipa_de` is empty when unset.
+    pub fn export_csv(&self) -> String {
+        let mut out = String::new();
+        out.push_str(CSV_HEADER);
+        out.push_str("\r\n");
+
+        for entry in sorted_entries(self) {
+            let related = entry.related_concepts.join(";");
+            let fields = [
+                entry.canonical_id.as_str(),
+                entry.german_label.as_str(),
+                entry.english_label.as_str(),
+                entry.german_definition.as_str(),
+                entry.english_definition.as_str(),
+                entry.pronunciation_de.as_str(),
+                entry.ipa_de.as_deref().unwrap_or_default(),
+                entry.word_type.as_str(),
+                related.as_str(),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| escape_csv(f)).collect();
+            out.push_str(&row.join(","));
+            out.push_str("\r\n");
+        }
+
+        out
+    }
+
+    /// Renders the lexicon as a Markdown glossary with one section per entry,
+    /// sorted by id. Each section carries an anchor named after the entry's
+    /// `canonical_id`; related concepts link to those anchors when the
+    /// lexicon has an entry for them.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Knotenlexikon\n");
+
+        for entry in sorted_entries(self) {
+            out.push_str(&format!("\n<a id=\"{}\"></a>\n", entry.canonical_id));
+            out.push_str(&format!(
+                "## {} / {}\n\n",
+                entry.german_label, entry.english_label
+            ));
+            out.push_str(&format!("*{}*\n\n", entry.word_type));
+            match self.ipa(&entry.canonical_id) {
+                Some(ipa) => out.push_str(&format!(
+                    "**Aussprache:** {} (/{}/)\n\n",
+                    entry.pronunciation_de, ipa
+                )),
+                None => out.push_str(&format!("**Aussprache:** {}\n\n", entry.pronunciation_de)),
+            }
+            out.push_str(&format!("- **DE:** {}\n", entry.german_definition));
+            out.push_str(&format!("- **EN:** {}\n", entry.english_definition));
+
+            if !entry.related_concepts.is_empty() {
+                let links: Vec<String> = entry
+                    .related_concepts
+                    .iter()
+                    .map(|id| match self.get_entry(id) {
+                        Some(related) => format!("[{}](#{})", related.german_label, id),
+                        None => format!("`{}`", id),
+                    })
+                    .collect();
+                out.push_str(&format!("\n**Siehe auch:** {}\n", links.join(", ")));
+            }
+        }
+
+        out
+    }
+}
+
+fn sorted_entries(store: &KnotenlexikonStore) -> Vec<&LemmaEntry> {
+    let mut entries = store.get_all_entries();
+    entries.sort_by(|a, b| a.canonical_id.cmp(&b.canonical_id));
+    entries
+}
+
+pub(crate) fn escape_xml(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_csv(input: &str) -> String {
+    if input.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", input.replace('"', "\"\""))
+    } else {
+        input.to_string()
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,10 +9,21 @@ pub struct LemmaEntry {
     pub german_definition: String,
     pub english_definition: String,
     pub pronunciation_de: String,
+    /// IPA transcription of the German label, e.g. for text-to-speech.
+    #[serde(default)]
+    pub ipa_de: Option<String>,
     pub word_type: String,
     pub related_concepts: Vec<String>,
 }
 
+/// Entries reachable from a lemma via `related_concepts`, plus the related ids
+/// that did not resolve to any entry.
+#[derive(Debug, Clone)]
+pub struct ConceptNeighborhood<'a> {
+    pub entries: Vec<&'a LemmaEntry>,
+    pub dangling: Vec<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct KnotenlexikonStore {
     entries: HashMap<String, LemmaEntry>,
@@ -35,6 +46,7 @@ impl KnotenlexikonStore {
             german_definition: "Ein computergestütztes System, das Code-Repositorien, Modelle und Protokolle als Organismusnetz interpretiert".to_string(),
             english_definition: "A system that treats code repositories, models, and logs as an organism-like network of nodes, neurochannels, clusters, and interfaces".to_string(),
             pronunciation_de: "NOY-ro-KNOH-ten-pfaat ZOOKH-ma-SHEE-nuh".to_string(),
+            ipa_de: Some("ˈnɔʏ̯ʁoˌknoːtn̩pfaːt ˈzuːxmaˌʃiːnə".to_string()),
             word_type: "feminine, die NeuroKnotenpfad-Suchmaschine".to_string(),
             related_concepts: vec![
                 "neuro_node_path".to_string(),
@@ -52,6 +64,7 @@ impl KnotenlexikonStore {
             german_definition: "Ein nachvollziehbarer Pfad durch ein Netzwerk von miteinander verbundenen Knoten".to_string(),
             english_definition: "A traceable path through an interconnected network of nodes".to_string(),
             pronunciation_de: "NOY-ro-KNOH-ten-pfaat".to_string(),
+            ipa_de: Some("ˈnɔʏ̯ʁoˌknoːtn̩pfaːt".to_string()),
             word_type: "masculine, der Neuroknotenpfad".to_string(),
             related_concepts: vec![
                 "cluster_path".to_string(),
@@ -66,6 +79,7 @@ impl KnotenlexikonStore {
             german_definition: "Ein Pfad durch thematisch zusammenhängende Cluster".to_string(),
             english_definition: "A path through thematically related clusters".to_string(),
             pronunciation_de: "KLUS-ter-pfaat".to_string(),
+            ipa_de: None,
             word_type: "masculine, der Clusterpfad".to_string(),
             related_concepts: vec!["cluster".to_string(), "neuro_node_path".to_string()],
         });
@@ -77,6 +91,7 @@ impl KnotenlexikonStore {
             german_definition: "Eine Schnittstelle für die Datenübertragung zwischen Neurokanälen".to_string(),
             english_definition: "An interface for data transmission between neural channels".to_string(),
             pronunciation_de: "NOY-ro-kah-NAHL-shhn-it-shteh-luh".to_string(),
+            ipa_de: None,
             word_type: "feminine, die NeurokanalSchnittstelle".to_string(),
             related_concepts: vec!["neuro_channel".to_string(), "interface".to_string()],
         });
@@ -90,6 +105,17 @@ impl KnotenlexikonStore {
         self.entries.get(canonical_id)
     }
 
+    /// IPA for an entry: the stored `ipa_de` if present, otherwise a
+    /// best-effort conversion of `pronunciation_de`. Returns `None` when the
+    /// respelling contains sounds the converter does not know.
+    pub fn ipa(&self, canonical_id: &str) -> Option<String> {
+        let entry = self.entries.get(canonical_id)?;
+        entry
+            .ipa_de
+            .clone()
+            .or_else(|| respelling_to_ipa(&entry.pronunciation_de))
+    }
+
     pub fn search_by_german(&self, term: &str) -> Vec<&LemmaEntry> {
         self.entries
             .values()
@@ -104,7 +130,251 @@ impl KnotenlexikonStore {
             .collect()
     }
 
+    /// Ranks entries by edit distance between `term` and their labels or
+    /// definition words, ignoring case and umlaut spelling (`ä` matches `ae`).
+    /// Scores are in `0.0..=1.0`, highest first.
+    pub fn search_fuzzy(&self, term: &str, max_distance: usize) -> Vec<(&LemmaEntry, f64)> {
+        let needle = normalize_for_matching(term);
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results: Vec<(&LemmaEntry, f64)> = self
+            .entries
+            .values()
+            .filter_map(|entry| {
+                let best = fuzzy_candidates(entry)
+                    .map(|candidate| {
+                        let distance = levenshtein(&needle, &candidate);
+                        let longest = needle.chars().count().max(candidate.chars().count());
+                        (distance, 1.0 - distance as f64 / longest as f64)
+                    })
+                    .filter(|(distance, _)| *distance <= max_distance)
+                    .map(|(_, score)| score)
+                    .fold(None, |best: Option<f64>, score| {
+                        Some(best.map_or(score, |b| b.max(score)))
+                    })?;
+                Some((entry, best))
+            })
+            .collect();
+
+        results.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.canonical_id.cmp(&b.0.canonical_id))
+        });
+        results
+    }
+
+    /// Breadth-first expansion over `related_concepts` up to `depth` hops,
+    /// excluding the starting entry. Returns `None` if `canonical_id` is unknown.
+    pub fn related_closure(&self, canonical_id: &str, depth: usize) -> Option<ConceptNeighborhood<'_>> {
+        let root = self.entries.get(canonical_id)?;
+
+        let mut neighborhood = ConceptNeighborhood {
+            entries: Vec::new(),
+            dangling: Vec::new(),
+        };
+        let mut seen: HashSet<&str> = HashSet::from([root.canonical_id.as_str()]);
+        let mut queue = VecDeque::from([(root, 0)]);
+
+        while let Some((entry, level)) = queue.pop_front() {
+            if level >= depth {
+                continue;
+            }
+            for related_id in &entry.related_concepts {
+                if !seen.insert(related_id.as_str()) {
+                    continue;
+                }
+                match self.entries.get(related_id) {
+                    Some(related) => {
+                        neighborhood.entries.push(related);
+                        queue.push_back((related, level + 1));
+                    }
+                    None => neighborhood.dangling.push(related_id.clone()),
+                }
+            }
+        }
+
+        Some(neighborhood)
+    }
+
+    /// Follow-up queries for `query`: each whitespace-separated term that
+    /// names a lemma (by id, English or German label, ignoring case and
+    /// separators) contributes the labels of its related concepts, in the
+    /// language of the matched label. Related ids without an entry are
+    /// skipped, and suggestions naming an original term are dropped.
+    pub fn suggest_related(&self, query: &str) -> Vec<String> {
+        let terms: HashSet<String> = query.split_whitespace().map(lemma_key).collect();
+        let mut seen: HashSet<String> = terms.clone();
+        let mut suggestions = Vec::new();
+
+        let mut matched: Vec<(&LemmaEntry, bool)> = self
+            .entries
+            .values()
+            .filter_map(|entry| {
+                if terms.contains(&lemma_key(&entry.german_label)) {
+                    Some((entry, true))
+                } else if terms.contains(&lemma_key(&entry.english_label))
+                    || terms.contains(&lemma_key(&entry.canonical_id))
+                {
+                    Some((entry, false))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        matched.sort_by(|a, b| a.0.canonical_id.cmp(&b.0.canonical_id));
+
+        for (entry, german) in matched {
+            for related in entry
+                .related_concepts
+                .iter()
+                .filter_map(|id| self.entries.get(id))
+            {
+                let label = if german {
+                    &related.german_label
+                } else {
+                    &related.english_label
+                };
+                if seen.insert(lemma_key(label)) && !terms.contains(&lemma_key(&related.canonical_id)) {
+                    suggestions.push(label.clone());
+                }
+            }
+        }
+
+        suggestions
+    }
+
     pub fn get_all_entries(&self) -> Vec<&LemmaEntry> {
         self.entries.values().collect()
     }
 }
+
+fn fuzzy_candidates(entry: &LemmaEntry) -> impl Iterator<Item = String> + '_ {
+    let labels = [&entry.german_label, &entry.english_label];
+    let texts = [
+        &entry.german_label,
+        &entry.english_label,
+        &entry.german_definition,
+        &entry.english_definition,
+    ];
+
+    labels
+        .into_iter()
+        .map(|label| normalize_for_matching(label))
+        .chain(texts.into_iter().flat_map(|text| {
+            text.split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(normalize_for_matching)
+        }))
+}
+
+/// Respelling fragments and their IPA, longest first so that greedy matching
+/// prefers digraphs like `oy` over `o`.
+const RESPELLING_TO_IPA: &[(&str, &str)] = &[
+    ("pf", "pf"),
+    ("oy", "ɔʏ"),
+    ("oh", "oː"),
+    ("aa", "aː"),
+    ("ah", "aː"),
+    ("ee", "iː"),
+    ("oo", "uː"),
+    ("uh", "ə"),
+    ("eh", "ɛ"),
+    ("sh", "ʃ"),
+    ("kh", "x"),
+    ("ts", "ts"),
+    ("a", "a"),
+    ("e", "ɛ"),
+    ("i", "ɪ"),
+    ("o", "o"),
+    ("u", "ʊ"),
+    ("b", "b"),
+    ("d", "d"),
+    ("f", "f"),
+    ("g", "ɡ"),
+    ("h", "h"),
+    ("k", "k"),
+    ("l", "l"),
+    ("m", "m"),
+    ("n", "n"),
+    ("p", "p"),
+    ("r", "ʁ"),
+    ("s", "s"),
+    ("t", "t"),
+    ("v", "v"),
+    ("y", "j"),
+    ("z", "z"),
+];
+
+/// Converts an ad-hoc respelling such as `KLUS-ter-pfaat` to IPA. Upper-case
+/// syllables are treated as stressed.
+fn respelling_to_ipa(respelling: &str) -> Option<String> {
+    let mut words = Vec::new();
+
+    for word in respelling.split_whitespace() {
+        let mut ipa = String::new();
+        for syllable in word.split('-').filter(|s| !s.is_empty()) {
+            if syllable.chars().any(|c| c.is_ascii_uppercase()) {
+                ipa.push('ˈ');
+            }
+            let lowered = syllable.to_ascii_lowercase();
+            let mut rest = lowered.as_str();
+            while !rest.is_empty() {
+                let (fragment, sound) = RESPELLING_TO_IPA
+                    .iter()
+                    .find(|(fragment, _)| rest.starts_with(fragment))?;
+                ipa.push_str(sound);
+                rest = &rest[fragment.len()..];
+            }
+        }
+        words.push(ipa);
+    }
+
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+fn normalize_for_matching(input: &str) -> String {
+    let mut normalized = String::with_capacity(input.len());
+    for c in input.trim().chars().flat_map(char::to_lowercase) {
+        match c {
+            'ä' => normalized.push_str("ae"),
+            'ö' => normalized.push_str("oe"),
+            'ü' => normalized.push_str("ue"),
+            'ß' => normalized.push_str("ss"),
+            _ => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+/// Matching key for lemma lookup: normalized and stripped of separators, so
+/// `cluster_path`, `ClusterPath` and `Cluster-Path` coincide.
+fn lemma_key(input: &str) -> String {
+    normalize_for_matching(input)
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()]
+}
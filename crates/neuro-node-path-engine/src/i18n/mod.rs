@@ -1,7 +1,10 @@
 pub mod translator;
 pub mod lemma_store;
 pub mod definitions;
+pub mod export;
+pub mod stop_words;
 
-pub use translator::Translator;
+pub use translator::{ChainedTranslation, Translator};
 pub use lemma_store::KnotenlexikonStore;
 pub use definitions::LemmaDefinition;
+pub use stop_words::StopWords;
@@ -0,0 +1,72 @@
+use std::collections::{HashMap, HashSet};
+
+const ENGLISH: &[&str] = &[
+    "a", "about", "all", "an", "and", "any", "are", "as", "at", "be", "by", "can", "does", "find",
+    "for", "from", "get", "has", "have", "how", "in", "into", "is", "it", "its", "list", "me", "of",
+    "on", "or", "show", "that", "the", "their", "them", "then", "there", "these", "this", "those",
+    "to", "was", "were", "what", "when", "where", "which", "who", "why", "will", "with",
+];
+
+const GERMAN: &[&str] = &[
+    "alle", "als", "am", "an", "auch", "auf", "aus", "bei", "das", "dass", "dem", "den", "der",
+    "des", "die", "ein", "eine", "einem", "einen", "einer", "eines", "finde", "für", "im", "in",
+    "ist", "mit", "nach", "nicht", "oder", "sind", "über", "und", "von", "was", "welche", "wie",
+    "wo", "zeige", "zu", "zum", "zur",
+];
+
+/// Words ignored when extracting query entities and ranking summary
+/// sentences, per language code. Matching is case-insensitive.
+#[derive(Debug, Clone)]
+pub struct StopWords {
+    by_language: HashMap<String, HashSet<String>>,
+}
+
+impl StopWords {
+    /// An empty list for every language.
+    pub fn none() -> Self {
+        Self {
+            by_language: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, language: &str, word: &str) {
+        self.by_language
+            .entry(language.to_string())
+            .or_default()
+            .insert(word.to_lowercase());
+    }
+
+    pub fn remove(&mut self, language: &str, word: &str) -> bool {
+        self.by_language
+            .get_mut(language)
+            .is_some_and(|words| words.remove(&word.to_lowercase()))
+    }
+
+    /// Whether `word` is a stop word in `language`. Unknown languages have
+    /// none.
+    pub fn contains(&self, language: &str, word: &str) -> bool {
+        self.by_language
+            .get(language)
+            .is_some_and(|words| words.contains(&word.to_lowercase()))
+    }
+
+    /// Whether `word` is a stop word in any language, for text whose
+    /// language is not known.
+    pub fn contains_any(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        self.by_language.values().any(|words| words.contains(&word))
+    }
+}
+
+/// The built-in English (`en`) and German (`de`) lists.
+impl Default for StopWords {
+    fn default() -> Self {
+        let mut stop_words = Self::none();
+        for (language, words) in [("en", ENGLISH), ("de", GERMAN)] {
+            for word in words {
+                stop_words.add(language, word);
+            }
+        }
+        stop_words
+    }
+}
@@ -1,7 +1,18 @@
 use std::collections::HashMap;
 
+/// Outcome of [`Translator::translate_chain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainedTranslation {
+    pub text: String,
+    /// Language `text` is in, or `None` when no language in the chain had
+    /// the term and `text` is the untranslated original.
+    pub language: Option<String>,
+    /// The first language of the chain did not have the term.
+    pub fallback: bool,
+}
+
 pub struct Translator {
-    translations: HashMap<(String, String), String>,
+    translations: HashMap<(String, String, String), String>,
 }
 
 impl Translator {
@@ -35,6 +46,36 @@ impl Translator {
             .get(&(from.to_string(), to.to_string(), term.to_string()))
             .cloned()
     }
+
+    /// Translates `term` from `from` into the first of `languages` that has
+    /// it, falling back to the original term when none does. A language
+    /// equal to `from` always matches with the term itself.
+    pub fn translate_chain(
+        &self,
+        from: &str,
+        term: &str,
+        languages: &[String],
+    ) -> ChainedTranslation {
+        for (position, language) in languages.iter().enumerate() {
+            let hit = if language == from {
+                Some(term.to_string())
+            } else {
+                self.translate(from, language, term)
+            };
+            if let Some(text) = hit {
+                return ChainedTranslation {
+                    text,
+                    language: Some(language.clone()),
+                    fallback: position > 0,
+                };
+            }
+        }
+        ChainedTranslation {
+            text: term.to_string(),
+            language: None,
+            fallback: true,
+        }
+    }
 }
 
 impl Default for Translator {
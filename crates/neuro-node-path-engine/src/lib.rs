@@ -4,11 +4,21 @@ pub mod repository;
 pub mod i18n;
 pub mod validation;
 pub mod utils;
+pub mod error;
+pub mod store;
+pub mod registry;
+
+use core::channel::SignalType;
+use std::collections::HashMap;
 
 pub use core::{node::Node, cluster::Cluster, channel::NeuralChannel, interface::Interface};
 pub use engine::{NeuroNodePathEngine, QueryContext};
+pub use error::EngineError;
 pub use i18n::KnotenlexikonStore;
-pub use validation::DualPathValidator;
+pub use registry::{EngineRegistry, SharedEngine};
+pub use repository::ConfigFileSet;
+pub use store::{GraphStore, InMemoryGraphStore, Table};
+pub use validation::{DualPathConfig, DualPathValidator};
 
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
@@ -18,6 +28,33 @@ pub struct EngineConfig {
     pub audit_retention_days: u32,
     pub supported_languages: Vec<String>,
     pub parallel_traversal: bool,
+    pub dual_path: DualPathConfig,
+    /// Files indexed as [`core::node::NodeType::Config`] instead of `File`.
+    pub config_files: ConfigFileSet,
+    /// Multiplier applied to channel weights by signal type during weighted
+    /// path resolution. Types without an entry weigh 1.0.
+    pub signal_weights: HashMap<SignalType, f64>,
+    /// Makes engine output reproducible: ids the engine assigns and query
+    /// timestamps come from a [`utils::DeterministicSource`] with this seed,
+    /// and query candidates are visited in id order.
+    pub seed: Option<u64>,
+    /// Default time budget for path resolution per query; see
+    /// [`engine::QueryContext::deadline`].
+    pub query_timeout: Option<std::time::Duration>,
+    /// Largest node count indexing may produce; see
+    /// [`EngineError::GraphTooLarge`].
+    pub max_nodes: Option<usize>,
+    /// Largest channel count indexing may produce, counting the `mod`
+    /// dependency channels a run adds; see [`EngineError::GraphTooLarge`].
+    pub max_channels: Option<usize>,
+    /// Match queries against normalized node names, so `user name` finds
+    /// `getUserName` and `get_user_name`; see [`utils::normalize_name`].
+    pub normalize_names: bool,
+    /// Words left out of query entities and cluster summary ranking.
+    pub stop_words: i18n::StopWords,
+    /// Comment markers recorded as technical debt while indexing; see
+    /// [`NeuroNodePathEngine::debt_markers`].
+    pub debt_markers: Vec<String>,
 }
 
 impl Default for EngineConfig {
@@ -29,6 +66,157 @@ impl Default for EngineConfig {
             audit_retention_days: 365,
             supported_languages: vec!["en".to_string(), "de".to_string()],
             parallel_traversal: true,
+            dual_path: DualPathConfig::default(),
+            config_files: ConfigFileSet::default(),
+            signal_weights: HashMap::new(),
+            seed: None,
+            query_timeout: None,
+            max_nodes: None,
+            max_channels: None,
+            normalize_names: false,
+            stop_words: i18n::StopWords::default(),
+            debt_markers: repository::debt_markers::DEFAULT_DEBT_MARKERS
+                .iter()
+                .map(|m| m.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl EngineConfig {
+    /// A builder starting from [`EngineConfig::default`].
+    pub fn builder() -> EngineConfigBuilder {
+        EngineConfigBuilder::default()
+    }
+
+    pub fn signal_weight(&self, signal_type: &SignalType) -> f64 {
+        self.signal_weights.get(signal_type).copied().unwrap_or(1.0)
+    }
+}
+
+/// Fluent construction of an [`EngineConfig`], e.g.
+/// `EngineConfig::builder().max_depth(16).enable_audit(false).build()`.
+/// Fields not set keep their default.
+#[derive(Debug, Clone, Default)]
+pub struct EngineConfigBuilder {
+    config: EngineConfig,
+}
+
+impl EngineConfigBuilder {
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.config.max_depth = max_depth;
+        self
+    }
+
+    pub fn enable_clustering(mut self, enable: bool) -> Self {
+        self.config.enable_clustering = enable;
+        self
+    }
+
+    pub fn enable_audit(mut self, enable: bool) -> Self {
+        self.config.enable_audit = enable;
+        self
+    }
+
+    pub fn audit_retention_days(mut self, days: u32) -> Self {
+        self.config.audit_retention_days = days;
+        self
+    }
+
+    pub fn supported_languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.supported_languages = languages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn parallel_traversal(mut self, enable: bool) -> Self {
+        self.config.parallel_traversal = enable;
+        self
+    }
+
+    pub fn dual_path(mut self, dual_path: DualPathConfig) -> Self {
+        self.config.dual_path = dual_path;
+        self
+    }
+
+    pub fn config_files(mut self, config_files: ConfigFileSet) -> Self {
+        self.config.config_files = config_files;
+        self
+    }
+
+    /// Sets the weight of one signal type, keeping the others.
+    pub fn signal_weight(mut self, signal_type: SignalType, weight: f64) -> Self {
+        self.config.signal_weights.insert(signal_type, weight);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn query_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.config.query_timeout = Some(timeout);
+        self
+    }
+
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.config.max_nodes = Some(max_nodes);
+        self
+    }
+
+    pub fn max_channels(mut self, max_channels: usize) -> Self {
+        self.config.max_channels = Some(max_channels);
+        self
+    }
+
+    pub fn normalize_names(mut self, enable: bool) -> Self {
+        self.config.normalize_names = enable;
+        self
+    }
+
+    pub fn stop_words(mut self, stop_words: i18n::StopWords) -> Self {
+        self.config.stop_words = stop_words;
+        self
+    }
+
+    pub fn debt_markers<I, S>(mut self, markers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.debt_markers = markers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Checks that `max_depth` is positive, at least one language is
+    /// supported, signal weights are finite and non-negative, and the query
+    /// timeout is not zero.
+    pub fn build(self) -> anyhow::Result<EngineConfig> {
+        let config = self.config;
+        if config.max_depth == 0 {
+            anyhow::bail!("max_depth must be greater than 0");
+        }
+        if config.supported_languages.is_empty() {
+            anyhow::bail!("supported_languages must not be empty");
+        }
+        if let Some((signal_type, weight)) = config
+            .signal_weights
+            .iter()
+            .find(|(_, weight)| !weight.is_finite() || **weight < 0.0)
+        {
+            anyhow::bail!(
+                "Signal weight for {} must be finite and non-negative, got {}",
+                signal_type,
+                weight
+            );
+        }
+        if config.query_timeout.is_some_and(|timeout| timeout.is_zero()) {
+            anyhow::bail!("query_timeout must not be zero");
         }
+        Ok(config)
     }
 }
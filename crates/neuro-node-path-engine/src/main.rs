@@ -3,26 +3,19 @@ use neuro_node_path_engine::{
     repository::CodeRepository,
 };
 use std::path::PathBuf;
-use tracing_subscriber;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .init();
+    init_tracing();
 
-    let config = EngineConfig {
-        max_depth: 32,
-        enable_clustering: true,
-        enable_audit: true,
-        audit_retention_days: 365,
-        supported_languages: vec!["en".to_string(), "de".to_string(), "es".to_string()],
-        parallel_traversal: true,
-    };
+    let config = EngineConfig::builder()
+        .supported_languages(["en", "de", "es"])
+        .build()?;
 
     let mut engine = NeuroNodePathEngine::new(config)?;
     let knotenlexikon = KnotenlexikonStore::default();
     engine.set_lemma_store(knotenlexikon);
+    engine.warm().await?;
 
     let repo_path = PathBuf::from("./target_repository");
     let repository = CodeRepository::new(repo_path)?;
@@ -30,10 +23,20 @@ async fn main() -> anyhow::Result<()> {
     engine.index_repository(&repository).await?;
 
     let query = "neural clustering patterns in async module";
-    let result = engine.query(query).await?;
+    let language = std::env::var("QUERY_LANG").unwrap_or_else(|_| "en".to_string());
+    let result = engine.query_in(query, &language).await?;
     
     println!("Query: {}", query);
     println!("Result: {:#?}", result);
 
     Ok(())
 }
+
+/// Logs as JSON when `LOG_FORMAT=json`, otherwise as human-readable text.
+fn init_tracing() {
+    let builder = tracing_subscriber::fmt().with_max_level(tracing::Level::INFO);
+    match std::env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => builder.json().init(),
+        _ => builder.init(),
+    }
+}
@@ -0,0 +1,56 @@
+use crate::NeuroNodePathEngine;
+use dashmap::DashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// An engine shared between request handlers: read-locked for queries,
+/// write-locked for indexing.
+pub type SharedEngine = Arc<RwLock<NeuroNodePathEngine>>;
+
+/// Engines by name, such as one per tenant, safe to use from any thread.
+///
+/// Handles from [`EngineRegistry::get`] keep their engine alive, so
+/// removing or replacing an engine does not disturb queries already
+/// running on it; only later lookups see the change.
+#[derive(Default)]
+pub struct EngineRegistry {
+    engines: DashMap<String, SharedEngine>,
+}
+
+impl EngineRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `engine` under `name` and returns its handle. An engine
+    /// already registered under `name` is replaced.
+    pub fn register(&self, name: impl Into<String>, engine: NeuroNodePathEngine) -> SharedEngine {
+        let shared = Arc::new(RwLock::new(engine));
+        self.engines.insert(name.into(), shared.clone());
+        shared
+    }
+
+    pub fn get(&self, name: &str) -> Option<SharedEngine> {
+        self.engines.get(name).map(|engine| engine.clone())
+    }
+
+    /// Unregisters the engine under `name`, returning its handle.
+    pub fn remove(&self, name: &str) -> Option<SharedEngine> {
+        self.engines.remove(name).map(|(_, engine)| engine)
+    }
+
+    /// Registered names, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.engines.iter().map(|entry| entry.key().clone()).collect();
+        names.sort();
+        names
+    }
+
+    pub fn len(&self) -> usize {
+        self.engines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.engines.is_empty()
+    }
+}
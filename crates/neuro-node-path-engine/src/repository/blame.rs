@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Authors of the most lines of `file` according to `git blame`, most lines
+/// first and by name on ties, at most `limit` of them. `repo_root` is the
+/// working tree `file` belongs to. Fails if git is missing or the file is
+/// not tracked.
+pub fn top_authors(repo_root: &Path, file: &str, limit: usize) -> anyhow::Result<Vec<String>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_root)
+        .args(["blame", "--line-porcelain", "--"])
+        .arg(file)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git blame failed for {}: {}",
+            file,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let mut lines_by_author: HashMap<String, usize> = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(author) = line.strip_prefix("author ") {
+            *lines_by_author.entry(author.to_string()).or_default() += 1;
+        }
+    }
+
+    let mut authors: Vec<(String, usize)> = lines_by_author.into_iter().collect();
+    authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(authors.into_iter().take(limit).map(|(author, _)| author).collect())
+}
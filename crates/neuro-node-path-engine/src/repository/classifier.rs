@@ -0,0 +1,40 @@
+use crate::core::node::NodeType;
+
+/// Assigns a [`NodeType`] to an indexed file, for file kinds the built-in
+/// types do not cover. Returning `None` defers to the next classifier.
+pub trait NodeClassifier: Send + Sync {
+    fn classify(&self, path: &str, content: &str) -> Option<NodeType>;
+}
+
+/// Classifiers ordered by priority, highest first. Classifiers with equal
+/// priority run in registration order.
+#[derive(Default)]
+pub struct ClassifierRegistry {
+    classifiers: Vec<(i32, Box<dyn NodeClassifier>)>,
+}
+
+impl ClassifierRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, priority: i32, classifier: Box<dyn NodeClassifier>) {
+        let position = self
+            .classifiers
+            .iter()
+            .position(|(p, _)| *p < priority)
+            .unwrap_or(self.classifiers.len());
+        self.classifiers.insert(position, (priority, classifier));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classifiers.is_empty()
+    }
+
+    /// Returns the first classifier's answer, in priority order.
+    pub fn classify(&self, path: &str, content: &str) -> Option<NodeType> {
+        self.classifiers
+            .iter()
+            .find_map(|(_, c)| c.classify(path, content))
+    }
+}
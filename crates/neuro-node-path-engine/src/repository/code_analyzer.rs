@@ -1,32 +1,72 @@
+use crate::core::interface::MethodSignature;
+use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+const CODE_PATTERNS: &[(&str, &str)] = &[
+    (r"(?m)^(pub\s+)?async\s+fn\s+(\w+)", "async_function"),
+    (r"(?m)^(pub\s+)?fn\s+(\w+)", "function"),
+    (r"(?m)^(pub\s+)?(struct|enum|trait)\s+(\w+)", "data_structure"),
+    (r"(?m)^mod\s+(\w+)", "module"),
+];
+
+static COMPILED_CODE_PATTERNS: Lazy<Result<Vec<(Regex, String)>, regex::Error>> =
+    Lazy::new(|| {
+        CODE_PATTERNS
+            .iter()
+            .map(|(pattern, entity_type)| Ok((Regex::new(pattern)?, entity_type.to_string())))
+            .collect()
+    });
+
+const FN_SIGNATURE: &str =
+    r"fn\s+(\w+)\s*(?:<[^>]*>)?\s*\(([^)]*)\)\s*(?:->\s*([^{;]+))?";
+
+static PUBLIC_FN: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(&format!(r"(?m)^\s*pub\s+(?:async\s+)?{}", FN_SIGNATURE)));
+
+static TRAIT_FN: Lazy<Result<Regex, regex::Error>> = Lazy::new(|| Regex::new(FN_SIGNATURE));
+
+static PUBLIC_TRAIT: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*pub\s+trait\s+(\w+)[^{]*\{"));
+
+static MOD_DECLARATION: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*;"));
 
 pub struct CodeAnalyzer {
     patterns: Vec<(Regex, String)>,
+    public_fn: &'static Regex,
+    trait_fn: &'static Regex,
+    public_trait: &'static Regex,
+    mod_declaration: &'static Regex,
 }
 
 impl CodeAnalyzer {
+    /// Builds an analyzer from the shared, lazily compiled pattern set.
+    ///
+    /// Panics if a built-in pattern fails to compile; use [`CodeAnalyzer::try_new`]
+    /// where a startup failure must be reported instead.
     pub fn new() -> Self {
-        let patterns = vec![
-            (
-                Regex::new(r"(?m)^(pub\s+)?async\s+fn\s+(\w+)").unwrap(),
-                "async_function".to_string(),
-            ),
-            (
-                Regex::new(r"(?m)^(pub\s+)?fn\s+(\w+)").unwrap(),
-                "function".to_string(),
-            ),
-            (
-                Regex::new(r"(?m)^(pub\s+)?(struct|enum|trait)\s+(\w+)").unwrap(),
-                "data_structure".to_string(),
-            ),
-            (
-                Regex::new(r"(?m)^mod\s+(\w+)").unwrap(),
-                "module".to_string(),
-            ),
-        ];
-
-        Self { patterns }
+        Self::try_new().expect("built-in code patterns must compile")
+    }
+
+    pub fn try_new() -> anyhow::Result<Self> {
+        let patterns = COMPILED_CODE_PATTERNS
+            .as_ref()
+            .map_err(|e| anyhow::anyhow!("Invalid code pattern: {}", e))?;
+
+        Ok(Self {
+            patterns: patterns.clone(),
+            public_fn: compiled(&PUBLIC_FN, "public fn")?,
+            trait_fn: compiled(&TRAIT_FN, "trait fn")?,
+            public_trait: compiled(&PUBLIC_TRAIT, "public trait")?,
+            mod_declaration: compiled(&MOD_DECLARATION, "mod declaration")?,
+        })
+    }
+
+    /// Compiles every lazily built pattern now instead of on first use,
+    /// returning the first one that fails to compile.
+    pub fn warm() -> anyhow::Result<()> {
+        Self::try_new().map(|_| ())
     }
 
     pub fn analyze_code(&self, code: &str) -> HashMap<String, Vec<String>> {
@@ -50,6 +90,130 @@ impl CodeAnalyzer {
 
         entities
     }
+
+    /// Number of entities of each kind [`Self::analyze_code`] finds in
+    /// `code`. Every kind is present, with a count of zero if absent.
+    pub fn entity_histogram(&self, code: &str) -> BTreeMap<String, usize> {
+        let mut histogram: BTreeMap<String, usize> = self
+            .patterns
+            .iter()
+            .map(|(_, entity_type)| (entity_type.clone(), 0))
+            .collect();
+        for (entity_type, names) in self.analyze_code(code) {
+            histogram.insert(entity_type, names.len());
+        }
+        histogram
+    }
+
+    /// Like [`Self::analyze_code`] for raw file contents; invalid UTF-8 is
+    /// replaced rather than rejected.
+    pub fn analyze_bytes(&self, bytes: &[u8]) -> HashMap<String, Vec<String>> {
+        self.analyze_code(&String::from_utf8_lossy(bytes))
+    }
+
+    /// Names of the out-of-line modules `code` declares with `mod name;`.
+    pub fn module_declarations(&self, code: &str) -> Vec<String> {
+        self.mod_declaration
+            .captures_iter(code)
+            .map(|caps| caps[1].to_string())
+            .collect()
+    }
+
+    /// Signatures of every `pub fn` in `code`, including methods in `impl`
+    /// blocks.
+    pub fn public_functions(&self, code: &str) -> Vec<MethodSignature> {
+        self.public_fn
+            .captures_iter(code)
+            .map(|c| signature_from(&c))
+            .collect()
+    }
+
+    /// Each `pub trait` in `code` with the signatures of its methods.
+    pub fn public_traits(&self, code: &str) -> Vec<(String, Vec<MethodSignature>)> {
+        self.public_trait
+            .captures_iter(code)
+            .map(|c| {
+                let body_start = c.get(0).map(|m| m.end()).unwrap_or_default();
+                let body = block_body(&code[body_start..]);
+                let methods = self
+                    .trait_fn
+                    .captures_iter(body)
+                    .map(|m| signature_from(&m))
+                    .collect();
+                (c[1].to_string(), methods)
+            })
+            .collect()
+    }
+}
+
+/// The compiled form of a lazily built signature pattern.
+fn compiled(
+    pattern: &'static Lazy<Result<Regex, regex::Error>>,
+    kind: &str,
+) -> anyhow::Result<&'static Regex> {
+    pattern
+        .as_ref()
+        .map_err(|e| anyhow::anyhow!("Invalid {} pattern: {}", kind, e))
+}
+
+/// Returns the text up to the brace closing a block whose opening brace has
+/// already been consumed.
+fn block_body(rest: &str) -> &str {
+    let mut depth = 1;
+    for (i, ch) in rest.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &rest[..i];
+                }
+            }
+            _ => {}
+        }
+    }
+    rest
+}
+
+fn signature_from(caps: &regex::Captures<'_>) -> MethodSignature {
+    MethodSignature {
+        name: caps[1].to_string(),
+        parameters: split_parameters(&caps[2]),
+        return_type: caps
+            .get(3)
+            .map(|m| m.as_str().trim().to_string())
+            .filter(|r| !r.is_empty())
+            .unwrap_or_else(|| "()".to_string()),
+        description: None,
+    }
+}
+
+/// Splits `a: T, b: U` into `(name, type)` pairs, skipping `self` receivers
+/// and ignoring commas nested in generics or tuples.
+fn split_parameters(params: &str) -> Vec<(String, String)> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, ch) in params.char_indices() {
+        match ch {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&params[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&params[start..]);
+
+    parts
+        .into_iter()
+        .filter_map(|p| {
+            let (name, ty) = p.split_once(':')?;
+            Some((name.trim().to_string(), ty.trim().to_string()))
+        })
+        .collect()
 }
 
 impl Default for CodeAnalyzer {
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+const DEFAULT_FILENAMES: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "package.json",
+    "package-lock.json",
+    "tsconfig.json",
+    "pyproject.toml",
+    "setup.cfg",
+    "requirements.txt",
+    "go.mod",
+    "go.sum",
+    "pom.xml",
+    "build.gradle",
+    "Makefile",
+    "Dockerfile",
+    ".gitignore",
+    ".editorconfig",
+];
+
+const DEFAULT_EXTENSIONS: &[&str] = &["toml", "yaml", "yml", "ini", "cfg", "conf", "lock"];
+
+/// Filenames and extensions that mark a file as configuration rather than code.
+///
+/// Filenames match exactly; extensions match case-insensitively.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigFileSet {
+    pub filenames: HashSet<String>,
+    pub extensions: HashSet<String>,
+}
+
+impl ConfigFileSet {
+    pub fn empty() -> Self {
+        Self {
+            filenames: HashSet::new(),
+            extensions: HashSet::new(),
+        }
+    }
+
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filenames.insert(filename.into());
+        self
+    }
+
+    pub fn with_extension(mut self, extension: impl Into<String>) -> Self {
+        self.extensions
+            .insert(extension.into().trim_start_matches('.').to_lowercase());
+        self
+    }
+
+    pub fn is_config(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        let by_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| self.filenames.contains(n));
+        let by_extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| self.extensions.contains(&e.to_lowercase()));
+        by_name || by_extension
+    }
+}
+
+impl Default for ConfigFileSet {
+    fn default() -> Self {
+        Self {
+            filenames: DEFAULT_FILENAMES.iter().map(|s| s.to_string()).collect(),
+            extensions: DEFAULT_EXTENSIONS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
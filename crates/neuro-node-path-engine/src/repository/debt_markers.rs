@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// Markers recognised when [`crate::EngineConfig::debt_markers`] is left at
+/// its default.
+pub const DEFAULT_DEBT_MARKERS: &[&str] = &["TODO", "FIXME", "HACK", "XXX"];
+
+const COMMENT_STARTS: &[&str] = &["//", "#", "/*", "--", "<!--", ";"];
+
+/// A technical-debt comment such as `// TODO: handle retries`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebtMarker {
+    pub marker: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// The comment after the marker, without a leading `:` or `(owner):`.
+    pub text: String,
+}
+
+/// Debt markers in the comments of `code`, one per line at most, in line
+/// order. A marker must be a whole word inside a comment; `todo_list` or a
+/// `"TODO"` string literal outside a comment do not count.
+pub fn extract_debt_markers(code: &str, markers: &[String]) -> Vec<DebtMarker> {
+    let mut found = Vec::new();
+    for (index, line) in code.lines().enumerate() {
+        let Some(comment_start) = comment_start(line) else {
+            continue;
+        };
+        let comment = &line[comment_start..];
+        let hit = markers
+            .iter()
+            .filter_map(|marker| whole_word(comment, marker).map(|at| (at, marker)))
+            .min_by_key(|(at, _)| *at);
+        if let Some((at, marker)) = hit {
+            found.push(DebtMarker {
+                marker: marker.clone(),
+                line: index + 1,
+                text: marker_text(&comment[at + marker.len()..]),
+            });
+        }
+    }
+    found
+}
+
+/// Where the comment on `line` starts, if it has one. Block comment
+/// continuation lines starting with `*` count as comments.
+fn comment_start(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('*') {
+        return Some(line.len() - trimmed.len());
+    }
+    COMMENT_STARTS.iter().filter_map(|start| line.find(start)).min()
+}
+
+fn whole_word(text: &str, word: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).map(|(at, _)| at).find(|&at| {
+        let before = text[..at].chars().next_back();
+        let after = text[at + word.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+fn marker_text(rest: &str) -> String {
+    let mut rest = rest.trim_start();
+    if rest.starts_with('(') {
+        if let Some(end) = rest.find(')') {
+            rest = &rest[end + 1..];
+        }
+    }
+    rest.trim_start_matches([':', '-'])
+        .trim()
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,123 @@
+use std::path::Path;
+
+/// Documentation comments in `code`, the source at `path`, with their
+/// markers stripped and blocks joined by blank lines in file order.
+///
+/// Rust `///` and `//!` lines, Python docstrings and Java/Kotlin `/** */`
+/// blocks are recognised; other languages yield `None`, as does a file
+/// without documentation.
+pub fn extract_documentation(path: &str, code: &str) -> Option<String> {
+    let extension = Path::new(path).extension()?.to_str()?;
+    let blocks = match extension {
+        "rs" => rust_line_docs(code),
+        "py" => python_docstrings(code),
+        "java" | "kt" | "kts" => javadoc_blocks(code),
+        _ => return None,
+    };
+
+    let blocks: Vec<String> = blocks.into_iter().filter(|b| !b.is_empty()).collect();
+    if blocks.is_empty() {
+        None
+    } else {
+        Some(blocks.join("\n\n"))
+    }
+}
+
+/// Runs of consecutive `///` or `//!` lines. `////` is an ordinary comment.
+fn rust_line_docs(code: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in code.lines() {
+        let trimmed = line.trim_start();
+        let text = if trimmed.starts_with("////") {
+            None
+        } else {
+            trimmed
+                .strip_prefix("///")
+                .or_else(|| trimmed.strip_prefix("//!"))
+        };
+        match text {
+            Some(text) => current.push(strip_one_space(text)),
+            None if !current.is_empty() => blocks.push(join_lines(current.drain(..))),
+            None => {}
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(join_lines(current.drain(..)));
+    }
+    blocks
+}
+
+/// Triple-quoted strings that open a line, as module, class and function
+/// docstrings do.
+fn python_docstrings(code: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut lines = code.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let unprefixed = trimmed.trim_start_matches(['r', 'R', 'u', 'U']);
+        let Some(delimiter) = ["\"\"\"", "'''"]
+            .into_iter()
+            .find(|d| unprefixed.starts_with(d))
+        else {
+            continue;
+        };
+
+        let rest = &unprefixed[delimiter.len()..];
+        if let Some(end) = rest.find(delimiter) {
+            blocks.push(rest[..end].trim().to_string());
+            continue;
+        }
+        let mut body = vec![rest.trim()];
+        for line in lines.by_ref() {
+            match line.find(delimiter) {
+                Some(end) => {
+                    body.push(line[..end].trim());
+                    break;
+                }
+                None => body.push(line.trim()),
+            }
+        }
+        blocks.push(join_lines(body.into_iter()));
+    }
+    blocks
+}
+
+/// `/** ... */` blocks with the leading `*` of each line removed.
+fn javadoc_blocks(code: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut rest = code;
+    while let Some(start) = rest.find("/**") {
+        let after = &rest[start + 3..];
+        // `/**/` is an empty ordinary comment.
+        if let Some(after_empty) = after.strip_prefix('/') {
+            rest = after_empty;
+            continue;
+        }
+        let Some(end) = after.find("*/") else {
+            break;
+        };
+        let lines = after[..end].lines().map(|line| {
+            let line = line.trim_start();
+            strip_one_space(line.strip_prefix('*').unwrap_or(line))
+        });
+        blocks.push(join_lines(lines));
+        rest = &after[end + 2..];
+    }
+    blocks
+}
+
+fn strip_one_space(text: &str) -> &str {
+    text.strip_prefix(' ').unwrap_or(text)
+}
+
+/// Joins `lines` with newlines, dropping blank lines at either end.
+fn join_lines<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    let lines: Vec<&str> = lines.map(str::trim_end).collect();
+    let first = lines.iter().position(|l| !l.is_empty());
+    let last = lines.iter().rposition(|l| !l.is_empty());
+    match (first, last) {
+        (Some(first), Some(last)) => lines[first..=last].join("\n"),
+        _ => String::new(),
+    }
+}
@@ -15,7 +15,7 @@ impl Indexer {
     pub fn index_node(&mut self, node: Node) {
         self.index
             .entry(node.node_type.to_string())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(node);
     }
 
@@ -56,6 +56,7 @@ impl std::fmt::Display for crate::core::node::NodeType {
             crate::core::node::NodeType::Protocol => write!(f, "protocol"),
             crate::core::node::NodeType::Model => write!(f, "model"),
             crate::core::node::NodeType::Interface => write!(f, "interface"),
+            crate::core::node::NodeType::Config => write!(f, "config"),
             crate::core::node::NodeType::Custom(s) => write!(f, "{}", s),
         }
     }
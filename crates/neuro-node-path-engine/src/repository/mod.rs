@@ -1,15 +1,34 @@
 pub mod indexer;
 pub mod code_analyzer;
 pub mod model_loader;
+pub mod config_files;
+pub mod classifier;
+pub mod doc_comments;
+pub mod blame;
+pub mod debt_markers;
 
 pub use indexer::Indexer;
 pub use code_analyzer::CodeAnalyzer;
+pub use config_files::ConfigFileSet;
+pub use classifier::{ClassifierRegistry, NodeClassifier};
+pub use debt_markers::{extract_debt_markers, DebtMarker};
+pub use doc_comments::extract_documentation;
 
-use std::path::PathBuf;
+use crate::utils::Hasher;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 pub struct CodeRepository {
     root_path: PathBuf,
+    max_depth: Option<usize>,
+}
+
+/// Files found by [`CodeRepository::scan`].
+#[derive(Debug, Clone, Default)]
+pub struct FileScan {
+    pub files: Vec<String>,
+    /// Files deeper than [`CodeRepository::with_max_depth`] allows.
+    pub skipped: usize,
 }
 
 impl CodeRepository {
@@ -17,26 +36,94 @@ impl CodeRepository {
         if !root_path.exists() {
             anyhow::bail!("Repository path does not exist: {:?}", root_path);
         }
-        Ok(Self { root_path })
+        Ok(Self {
+            root_path,
+            max_depth: None,
+        })
+    }
+
+    /// Only scans files at most `depth` levels below the root, the same
+    /// depth `WalkDir::max_depth` uses: `1` keeps only the root's own files.
+    /// Deeper directories are only listed, to count their files in
+    /// [`FileScan::skipped`].
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
     }
 
     pub async fn scan_files(&self) -> anyhow::Result<Vec<String>> {
-        let mut files = Vec::new();
+        Ok(self.scan().await?.files)
+    }
 
-        for entry in WalkDir::new(&self.root_path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().is_file())
-        {
+    /// Scanned files plus the number left out by the depth limit.
+    pub async fn scan(&self) -> anyhow::Result<FileScan> {
+        let mut scan = FileScan::default();
+
+        let mut walk = WalkDir::new(&self.root_path).sort_by_file_name();
+        if let Some(depth) = self.max_depth {
+            walk = walk.max_depth(depth);
+        }
+        for entry in walk.into_iter().filter_map(|e| e.ok()) {
+            if self.max_depth == Some(entry.depth()) && entry.file_type().is_dir() {
+                // The walk stops at this directory; its files are only
+                // counted.
+                scan.skipped += count_files(entry.path());
+                continue;
+            }
+            if !entry.path().is_file() {
+                continue;
+            }
             if let Some(path) = entry.path().to_str() {
-                files.push(path.to_string());
+                scan.files.push(path.to_string());
             }
         }
 
-        Ok(files)
+        Ok(scan)
+    }
+
+    /// Digest of every scanned file's root-relative path and content hash.
+    /// It changes exactly when a file is added, removed, renamed or edited.
+    pub async fn fingerprint(&self) -> anyhow::Result<String> {
+        let mut entries = Vec::new();
+        for file in self.scan_files().await? {
+            // Unreadable files still count, so they cannot fail fingerprinting.
+            let hash = match tokio::fs::read(&file).await {
+                Ok(content) => Hasher::hash_bytes(&content),
+                Err(_) => "unreadable".to_string(),
+            };
+            let relative = Path::new(&file)
+                .strip_prefix(&self.root_path)
+                .unwrap_or(Path::new(&file))
+                .to_string_lossy()
+                .into_owned();
+            entries.push((relative, hash));
+        }
+        entries.sort();
+
+        let manifest: String = entries
+            .iter()
+            .map(|(path, hash)| format!("{}\0{}\n", path, hash))
+            .collect();
+        Ok(Hasher::hash_string(&manifest))
     }
 
     pub fn get_root_path(&self) -> &PathBuf {
         &self.root_path
     }
+
+    /// The limit set with [`CodeRepository::with_max_depth`], if any.
+    pub fn get_max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+}
+
+/// Number of files anywhere below `directory`, taken from the directory
+/// listings without inspecting each file.
+fn count_files(directory: &Path) -> usize {
+    WalkDir::new(directory)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .count()
 }
@@ -1,6 +1,12 @@
+use crate::EngineError;
 use serde_json::Value;
+use std::io::Read;
 use std::path::Path;
 
+/// Deepest array/object nesting [`ModelLoader::load_json_model_limited`]
+/// accepts.
+pub const MAX_JSON_DEPTH: usize = 64;
+
 pub struct ModelLoader;
 
 impl ModelLoader {
@@ -9,6 +15,32 @@ impl ModelLoader {
         Ok(serde_json::from_str(&content)?)
     }
 
+    /// [`Self::load_json_model`] for untrusted files. Fails with
+    /// [`EngineError::ModelTooLarge`] before reading a file over `max_bytes`,
+    /// and with [`EngineError::ModelTooDeep`] before parsing JSON nested
+    /// deeper than [`MAX_JSON_DEPTH`].
+    pub fn load_json_model_limited(path: &Path, max_bytes: u64) -> anyhow::Result<Value> {
+        let size = std::fs::metadata(path)?.len();
+        if size > max_bytes {
+            return Err(EngineError::ModelTooLarge { limit: max_bytes, actual: size }.into());
+        }
+
+        // The file may have grown since the metadata check.
+        let mut content = String::new();
+        std::fs::File::open(path)?
+            .take(max_bytes + 1)
+            .read_to_string(&mut content)?;
+        let read = content.len() as u64;
+        if read > max_bytes {
+            return Err(EngineError::ModelTooLarge { limit: max_bytes, actual: read }.into());
+        }
+
+        if nesting_depth(&content) > MAX_JSON_DEPTH {
+            return Err(EngineError::ModelTooDeep { limit: MAX_JSON_DEPTH }.into());
+        }
+        Ok(serde_json::from_str(&content)?)
+    }
+
     pub fn load_protocol(path: &Path) -> anyhow::Result<String> {
         std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!(e))
     }
@@ -17,3 +49,31 @@ impl ModelLoader {
         model.is_object() || model.is_array()
     }
 }
+
+/// Deepest array/object nesting in `json`, ignoring brackets inside
+/// strings. Malformed input is measured as far as it goes.
+fn nesting_depth(json: &str) -> usize {
+    let (mut depth, mut deepest) = (0usize, 0usize);
+    let (mut in_string, mut escaped) = (false, false);
+    for byte in json.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                deepest = deepest.max(depth);
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    deepest
+}
@@ -0,0 +1,144 @@
+use crate::{
+    core::{Cluster, Interface, NeuralChannel, Node},
+    EngineError,
+};
+use dashmap::DashMap;
+
+/// One keyed collection in a [`GraphStore`]. Values are handed out as
+/// clones; changes go through [`Table::update`]. Every method fails with
+/// [`EngineError::Store`] when the backend does; the in-memory tables never
+/// fail.
+///
+/// Visitors passed to [`Table::for_each`] and [`Table::retain`] must not
+/// modify the same table.
+pub trait Table<T: Clone>: Send + Sync {
+    /// Inserts `value` under `id`, replacing any previous value.
+    fn insert(&self, id: String, value: T) -> Result<(), EngineError>;
+
+    fn get(&self, id: &str) -> Result<Option<T>, EngineError>;
+
+    fn contains(&self, id: &str) -> Result<bool, EngineError>;
+
+    /// Applies `change` to the value under `id`. Returns false if there is
+    /// no such value.
+    fn update(&self, id: &str, change: &mut dyn FnMut(&mut T)) -> Result<bool, EngineError>;
+
+    fn remove(&self, id: &str) -> Result<Option<T>, EngineError>;
+
+    fn len(&self) -> Result<usize, EngineError>;
+
+    fn is_empty(&self) -> Result<bool, EngineError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Calls `visit` with every value, in no particular order.
+    fn for_each(&self, visit: &mut dyn FnMut(&T)) -> Result<(), EngineError>;
+
+    /// Removes every value for which `keep` returns false.
+    fn retain(&self, keep: &mut dyn FnMut(&T) -> bool) -> Result<(), EngineError>;
+
+    /// Clones of every value, in no particular order.
+    fn values(&self) -> Result<Vec<T>, EngineError> {
+        self.filter(&mut |_| true)
+    }
+
+    /// Clones of the values for which `matches` returns true.
+    fn filter(&self, matches: &mut dyn FnMut(&T) -> bool) -> Result<Vec<T>, EngineError> {
+        let mut values = Vec::new();
+        self.for_each(&mut |value| {
+            if matches(value) {
+                values.push(value.clone());
+            }
+        })?;
+        Ok(values)
+    }
+}
+
+impl<T: Clone + Send + Sync> Table<T> for DashMap<String, T> {
+    fn insert(&self, id: String, value: T) -> Result<(), EngineError> {
+        DashMap::insert(self, id, value);
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<T>, EngineError> {
+        Ok(DashMap::get(self, id).map(|value| value.clone()))
+    }
+
+    fn contains(&self, id: &str) -> Result<bool, EngineError> {
+        Ok(self.contains_key(id))
+    }
+
+    fn update(&self, id: &str, change: &mut dyn FnMut(&mut T)) -> Result<bool, EngineError> {
+        Ok(match self.get_mut(id) {
+            Some(mut value) => {
+                change(&mut value);
+                true
+            }
+            None => false,
+        })
+    }
+
+    fn remove(&self, id: &str) -> Result<Option<T>, EngineError> {
+        Ok(DashMap::remove(self, id).map(|(_, value)| value))
+    }
+
+    fn len(&self) -> Result<usize, EngineError> {
+        Ok(DashMap::len(self))
+    }
+
+    fn for_each(&self, visit: &mut dyn FnMut(&T)) -> Result<(), EngineError> {
+        for entry in self.iter() {
+            visit(entry.value());
+        }
+        Ok(())
+    }
+
+    fn retain(&self, keep: &mut dyn FnMut(&T) -> bool) -> Result<(), EngineError> {
+        DashMap::retain(self, |_, value| keep(value));
+        Ok(())
+    }
+}
+
+/// Storage for the graph the engine queries. The engine only reaches its
+/// nodes, clusters, channels and interfaces through this trait, so a
+/// persistent backend can replace the default [`InMemoryGraphStore`] via
+/// [`crate::NeuroNodePathEngine::with_store`].
+pub trait GraphStore: Send + Sync {
+    fn nodes(&self) -> &dyn Table<Node>;
+    fn clusters(&self) -> &dyn Table<Cluster>;
+    fn channels(&self) -> &dyn Table<NeuralChannel>;
+    fn interfaces(&self) -> &dyn Table<Interface>;
+}
+
+/// The default store: one concurrent map per collection, lost on drop.
+#[derive(Default)]
+pub struct InMemoryGraphStore {
+    nodes: DashMap<String, Node>,
+    clusters: DashMap<String, Cluster>,
+    channels: DashMap<String, NeuralChannel>,
+    interfaces: DashMap<String, Interface>,
+}
+
+impl InMemoryGraphStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl GraphStore for InMemoryGraphStore {
+    fn nodes(&self) -> &dyn Table<Node> {
+        &self.nodes
+    }
+
+    fn clusters(&self) -> &dyn Table<Cluster> {
+        &self.clusters
+    }
+
+    fn channels(&self) -> &dyn Table<NeuralChannel> {
+        &self.channels
+    }
+
+    fn interfaces(&self) -> &dyn Table<Interface> {
+        &self.interfaces
+    }
+}
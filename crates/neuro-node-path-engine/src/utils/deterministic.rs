@@ -0,0 +1,42 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Reproducible ids and timestamps for [`crate::EngineConfig::seed`].
+///
+/// Ids are UUID-v4-shaped values drawn from a SplitMix64 stream, and
+/// timestamps come from a logical clock that starts at the Unix epoch and
+/// advances one second per call.
+pub struct DeterministicSource {
+    state: u64,
+    ticks: i64,
+}
+
+impl DeterministicSource {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed, ticks: 0 }
+    }
+
+    pub fn next_id(&mut self) -> String {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_le_bytes());
+        uuid::Builder::from_random_bytes(bytes).into_uuid().to_string()
+    }
+
+    pub fn next_timestamp(&mut self) -> DateTime<Utc> {
+        self.ticks += 1;
+        DateTime::UNIX_EPOCH + Duration::seconds(self.ticks)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
@@ -1,5 +1,9 @@
 pub mod serialization;
 pub mod hashing;
+pub mod deterministic;
+pub mod normalize;
 
 pub use serialization::Serializer;
 pub use hashing::Hasher;
+pub use deterministic::DeterministicSource;
+pub use normalize::normalize_name;
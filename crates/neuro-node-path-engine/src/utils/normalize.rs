@@ -0,0 +1,25 @@
+/// Lower-cased words of `name`, split at non-alphanumeric characters and at
+/// camelCase boundaries and joined by single spaces, so `getUserName`,
+/// `get_user_name` and `GetUserName` all become `get user name`. A run of
+/// capitals stays one word: `HTTPServer` becomes `http server`.
+pub fn normalize_name(name: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    for part in name.split(|c: char| !c.is_alphanumeric()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let boundary = i > 0
+                && c.is_uppercase()
+                && (!chars[i - 1].is_uppercase()
+                    || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+            if boundary && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(c.to_lowercase());
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words.join(" ")
+}
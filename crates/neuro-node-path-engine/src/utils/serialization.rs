@@ -11,7 +11,7 @@ impl Serializer {
         Ok(serde_json::to_string(data)?)
     }
 
-    pub fn from_json<'a, T: serde::de::DeserializeOwned>(json: &'a str) -> anyhow::Result<T> {
+    pub fn from_json<T: serde::de::DeserializeOwned>(json: &str) -> anyhow::Result<T> {
         Ok(serde_json::from_str(json)?)
     }
 }
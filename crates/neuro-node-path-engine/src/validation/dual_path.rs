@@ -1,30 +1,129 @@
-pub struct DualPathValidator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Overlap between two paths, as reported by
+/// [`DualPathValidator::cross_validate_detailed`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrossValidation {
+    /// Nodes present in both paths, in first-path order.
+    pub shared: Vec<String>,
+    /// Size of the intersection over size of the union of the two node sets.
+    pub jaccard: f64,
+    pub intersect: bool,
+}
+
+/// Thresholds for [`DualPathValidator::validate`].
+///
+/// The verdict is the first matching row of:
+///
+/// | condition                                                  | verdict |
+/// |------------------------------------------------------------|---------|
+/// | both paths empty                                           | INVALID |
+/// | `require_overlap` and the paths share no node              | INVALID |
+/// | node path shorter than `min_node_len`                      | WARNING |
+/// | cluster path shorter than `min_cluster_len`                | WARNING |
+/// | otherwise                                                  | VALID   |
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DualPathConfig {
+    pub min_node_len: usize,
+    pub min_cluster_len: usize,
+    pub require_overlap: bool,
+}
+
+impl Default for DualPathConfig {
+    fn default() -> Self {
+        Self {
+            min_node_len: 1,
+            min_cluster_len: 0,
+            require_overlap: false,
+        }
+    }
+}
+
+pub struct DualPathValidator {
+    config: DualPathConfig,
+}
 
 impl DualPathValidator {
-    pub fn new() -> Self {
-        Self
+    pub fn new(config: DualPathConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &DualPathConfig {
+        &self.config
     }
 
+    /// Validates the two paths, comparing their ids directly for overlap.
     pub fn validate(&self, node_path: &[String], cluster_path: &[String]) -> anyhow::Result<String> {
+        self.validate_with_members(node_path, cluster_path, cluster_path)
+    }
+
+    /// Validates the two paths, checking overlap between `node_path` and
+    /// `cluster_members`, the node ids belonging to the clusters on
+    /// `cluster_path`.
+    pub fn validate_with_members(
+        &self,
+        node_path: &[String],
+        cluster_path: &[String],
+        cluster_members: &[String],
+    ) -> anyhow::Result<String> {
         if node_path.is_empty() && cluster_path.is_empty() {
             return Ok("INVALID: No paths provided".to_string());
         }
 
-        if node_path.len() >= cluster_path.len() {
-            Ok("VALID: Dual path consensus established".to_string())
-        } else {
-            Ok("WARNING: Cluster path exceeds node path".to_string())
+        if self.config.require_overlap && !self.cross_validate(node_path, cluster_members) {
+            return Ok("INVALID: Node and cluster paths are disjoint".to_string());
         }
+
+        if node_path.len() < self.config.min_node_len {
+            return Ok(format!(
+                "WARNING: Node path shorter than {} nodes",
+                self.config.min_node_len
+            ));
+        }
+
+        if cluster_path.len() < self.config.min_cluster_len {
+            return Ok(format!(
+                "WARNING: Cluster path shorter than {} clusters",
+                self.config.min_cluster_len
+            ));
+        }
+
+        Ok("VALID: Dual path consensus established".to_string())
     }
 
     pub fn cross_validate(&self, path1: &[String], path2: &[String]) -> bool {
-        if path1.is_empty() || path2.is_empty() {
-            return false;
-        }
+        self.cross_validate_detailed(path1, path2).intersect
+    }
 
-        let set1: std::collections::HashSet<_> = path1.iter().collect();
-        let set2: std::collections::HashSet<_> = path2.iter().collect();
+    pub fn cross_validate_detailed(&self, path1: &[String], path2: &[String]) -> CrossValidation {
+        let set1: HashSet<&String> = path1.iter().collect();
+        let set2: HashSet<&String> = path2.iter().collect();
+
+        let mut seen = HashSet::new();
+        let shared: Vec<String> = path1
+            .iter()
+            .filter(|node| set2.contains(node) && seen.insert(*node))
+            .cloned()
+            .collect();
+
+        let union = set1.union(&set2).count();
+        let jaccard = if union == 0 {
+            0.0
+        } else {
+            shared.len() as f64 / union as f64
+        };
+
+        CrossValidation {
+            intersect: !shared.is_empty(),
+            shared,
+            jaccard,
+        }
+    }
+}
 
-        !set1.is_disjoint(&set2)
+impl Default for DualPathValidator {
+    fn default() -> Self {
+        Self::new(DualPathConfig::default())
     }
 }
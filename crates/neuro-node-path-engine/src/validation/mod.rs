@@ -1,5 +1,5 @@
 pub mod dual_path;
 pub mod consensus;
 
-pub use dual_path::DualPathValidator;
+pub use dual_path::{CrossValidation, DualPathConfig, DualPathValidator};
 pub use consensus::ConsensusValidator;
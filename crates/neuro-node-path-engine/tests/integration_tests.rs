@@ -1,10 +1,17 @@
 #[cfg(test)]
 mod integration_tests {
     use neuro_node_path_engine::{
-        EngineConfig, NeuroNodePathEngine, KnotenlexikonStore,
-        repository::CodeRepository,
+        core::{channel::SignalType, cluster::ClusterType, Cluster, interface::InterfaceType, node::NodeType, NeuralChannel, Neuron, Node},
+        ConfigFileSet, EngineConfig, EngineError, EngineRegistry, GraphStore, InMemoryGraphStore, NeuroNodePathEngine, KnotenlexikonStore, Table,
+        engine::{diff_results, pipeline, GraphFormat, audit_trail::{self, AuditSubscription}, AuditTrail, EngineObserver, LayoutAlgorithm, SmellKind, QueryResult, IndexPhase, QueryContext, LayerRule, QueryStage, QueryState, StepReason},
+        i18n::lemma_store::LemmaEntry,
+        repository::{model_loader::{ModelLoader, MAX_JSON_DEPTH}, CodeRepository, NodeClassifier},
     };
-    use std::path::PathBuf;
+    use ed25519_dalek::SigningKey;
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use tokio_util::sync::CancellationToken;
 
     #[tokio::test]
     async fn test_engine_initialization() {
@@ -13,6 +20,33 @@ mod integration_tests {
         assert!(engine.is_ok());
     }
 
+    struct BlockAllStage;
+
+    impl QueryStage for BlockAllStage {
+        fn name(&self) -> &str {
+            "block_all"
+        }
+
+        fn run(&self, state: &mut QueryState<'_>) -> anyhow::Result<()> {
+            state.validation_status = "BLOCKED: security filter".to_string();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_query_stage() {
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+
+        let mut stages = pipeline::default_pipeline(engine.config()).unwrap();
+        stages.push(Box::new(BlockAllStage));
+        engine.set_pipeline(stages);
+        assert_eq!(engine.pipeline_stage_names().last(), Some(&"block_all"));
+
+        let result = engine.query("neural path").await.unwrap();
+        assert_eq!(result.validation_status, "BLOCKED: security filter");
+        assert_eq!(result.audit_path.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_knotenlexikon_store() {
         let store = KnotenlexikonStore::new();
@@ -30,4 +64,1499 @@ mod integration_tests {
         let results = store.search_by_german("Knoten");
         assert!(!results.is_empty());
     }
+
+    #[test]
+    fn test_fuzzy_lemma_search() {
+        let store = KnotenlexikonStore::new();
+
+        let results = store.search_fuzzy("Neuroknotenpfat", 1);
+        assert_eq!(results[0].0.canonical_id, "neuro_node_path");
+        assert!(results[0].1 < 1.0);
+
+        let results = store.search_fuzzy("Datenuebertragung", 0);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.canonical_id, "neuro_channel_interface");
+        assert_eq!(results[0].1, 1.0);
+    }
+
+    #[test]
+    fn test_lexicon_export_escapes_special_characters() {
+        let mut store = KnotenlexikonStore::new();
+        store.add_entry(LemmaEntry {
+            canonical_id: "escape_probe".to_string(),
+            german_label: "Knoten & \"Pfad\"".to_string(),
+            english_label: "Node <Path>".to_string(),
+            german_definition: "Eins, zwei".to_string(),
+            english_definition: "One\ntwo".to_string(),
+            pronunciation_de: "KNOH-ten".to_string(),
+            ipa_de: Some("ˈknoːtn̩".to_string()),
+            word_type: "masculine".to_string(),
+            related_concepts: vec!["neuro_node_path".to_string()],
+        });
+
+        let tbx = store.export_tbx();
+        assert!(tbx.contains("<term>Knoten &amp; &quot;Pfad&quot;</term>"));
+        assert!(tbx.contains("<term>Node &lt;Path&gt;</term>"));
+        assert!(tbx.contains("<termNote type=\"pronunciation\">KNOH-ten</termNote>"));
+        let ipa_note = tbx
+            .split("<conceptEntry id=\"escape_probe\">")
+            .nth(1)
+            .and_then(|entry| entry.split("<termNote type=\"pronunciation\">/").nth(1))
+            .and_then(|rest| rest.split("/</termNote>").next());
+        assert_eq!(ipa_note, store.get_entry("escape_probe").unwrap().ipa_de.as_deref());
+
+        let csv = store.export_csv();
+        assert!(csv.starts_with("canonical_id,"));
+        assert!(csv.contains(",pronunciation_de,ipa_de,word_type,"));
+        assert!(csv.contains("escape_probe,\"Knoten & \"\"Pfad\"\"\",Node <Path>,\"Eins, zwei\",\"One\ntwo\",KNOH-ten,ˈknoːtn̩,masculine,"));
+    }
+
+    #[test]
+    fn test_lexicon_markdown_glossary() {
+        let markdown = KnotenlexikonStore::new().to_markdown();
+
+        assert!(markdown.starts_with("# Knotenlexikon\n"));
+        assert!(markdown.contains("<a id=\"neuro_node_path\"></a>\n## Neuroknotenpfad / NeuroNodePath\n"));
+        assert!(markdown.contains("**Aussprache:** NOY-ro-KNOH-ten-pfaat (/ˈnɔʏ̯ʁoˌknoːtn̩pfaːt/)"));
+        assert!(markdown.contains("- **EN:** A traceable path through an interconnected network of nodes\n"));
+        assert!(markdown.contains("**Siehe auch:** [Clusterpfad](#cluster_path), `audit_path`\n"));
+        let cluster_path = markdown.find("## Clusterpfad").unwrap();
+        let neuro_node_path = markdown.find("## Neuroknotenpfad /").unwrap();
+        assert!(cluster_path < neuro_node_path);
+    }
+
+    #[test]
+    fn test_related_concept_closure() {
+        let store = KnotenlexikonStore::new();
+
+        let direct = store.related_closure("neuro_node_path", 1).unwrap();
+        let ids: Vec<_> = direct.entries.iter().map(|e| e.canonical_id.as_str()).collect();
+        assert_eq!(ids, vec!["cluster_path"]);
+        assert_eq!(direct.dangling, vec!["audit_path".to_string()]);
+
+        let wider = store.related_closure("neuro_node_path", 2).unwrap();
+        assert_eq!(wider.entries.len(), 1);
+        assert!(wider.dangling.contains(&"cluster".to_string()));
+
+        assert!(store.related_closure("unknown_concept", 3).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_suggest_related_queries() {
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        engine.set_lemma_store(KnotenlexikonStore::new());
+
+        // neuro_node_path relates to cluster_path (named in the query) and
+        // audit_path (no lexicon entry), so only ClusterPath's relations remain.
+        let suggestions = engine.suggest_related("NeuroNodePath cluster_path").await;
+        assert!(suggestions.is_empty(), "{:?}", suggestions);
+
+        let suggestions = engine.suggest_related("Clusterpfad finden").await;
+        assert_eq!(suggestions, vec!["Neuroknotenpfad".to_string()]);
+
+        let suggestions = engine.suggest_related("find the neuronodepath").await;
+        assert_eq!(suggestions, vec!["ClusterPath".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_warm_populates_lexicon_idempotently() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        assert!(engine.suggest_related("Clusterpfad finden").await.is_empty());
+
+        engine.warm().await.unwrap();
+        engine.warm().await.unwrap();
+        let suggestions = engine.suggest_related("Clusterpfad finden").await;
+        assert_eq!(suggestions, vec!["Neuroknotenpfad".to_string()]);
+        assert!(engine.query("anything").await.is_ok());
+    }
+
+    #[test]
+    fn test_lemma_ipa() {
+        let store = KnotenlexikonStore::new();
+
+        assert_eq!(
+            store.ipa("neuro_node_path").as_deref(),
+            Some("ˈnɔʏ̯ʁoˌknoːtn̩pfaːt")
+        );
+        assert_eq!(store.ipa("cluster_path").as_deref(), Some("ˈklʊstɛʁpfaːt"));
+        assert!(store.ipa("unknown_concept").is_none());
+    }
+
+    #[test]
+    fn test_limited_model_loading() {
+        let dir = tempfile::tempdir().unwrap();
+        let model = dir.path().join("model.json");
+        std::fs::write(&model, r#"{"layers": [{"name": "[not nesting]"}]}"#).unwrap();
+        let loaded = ModelLoader::load_json_model_limited(&model, 1024).unwrap();
+        assert_eq!(loaded["layers"][0]["name"], "[not nesting]");
+
+        let error = ModelLoader::load_json_model_limited(&model, 10).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<EngineError>(),
+            Some(EngineError::ModelTooLarge { limit: 10, .. })
+        ));
+
+        let bomb = dir.path().join("bomb.json");
+        let depth = MAX_JSON_DEPTH + 1;
+        std::fs::write(&bomb, format!("{}{}", "[".repeat(depth), "]".repeat(depth))).unwrap();
+        let error = ModelLoader::load_json_model_limited(&bomb, 1024).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<EngineError>(),
+            Some(&EngineError::ModelTooDeep { limit: MAX_JSON_DEPTH })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_owners_from_git_blame() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |author: &str, args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(dir.path())
+                .args(["-c", "user.email=dev@example.com", "-c", &format!("user.name={}", author)])
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git("ada", &["init", "-q"]);
+        std::fs::write(dir.path().join("core.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        std::fs::write(dir.path().join("api.rs"), "fn api() {}\n").unwrap();
+        git("ada", &["add", "."]);
+        git("ada", &["commit", "-q", "-m", "initial"]);
+        std::fs::write(dir.path().join("api.rs"), "fn api() {}\nfn v2() {}\nfn v3() {}\n").unwrap();
+        git("grace", &["commit", "-q", "-am", "extend api"]);
+        std::fs::write(dir.path().join("untracked.rs"), "fn draft() {}\n").unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+
+        assert_eq!(engine.record_owners(&repository, 2).unwrap(), 2);
+        assert_eq!(engine.nodes_by_owner("ada").unwrap().len(), 2);
+        let grace = engine.nodes_by_owner("grace").unwrap();
+        assert_eq!(grace.len(), 1);
+        let api = grace[0].clone();
+        assert!(engine.get_node(&api).unwrap().unwrap().name.ends_with("api.rs"));
+        assert_eq!(
+            engine.get_node(&api).unwrap().unwrap().metadata.properties["owners"],
+            serde_json::json!(["grace", "ada"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_engine_registry_keeps_removed_engine_alive() {
+        let registry = Arc::new(EngineRegistry::new());
+        for tenant in ["acme", "globex"] {
+            let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+            engine.add_node(Node::new(format!("{}_module", tenant), NodeType::Module, String::new())).unwrap();
+            registry.register(tenant, engine);
+        }
+        assert_eq!(registry.names(), vec!["acme", "globex"]);
+
+        let in_flight = registry.get("acme").unwrap();
+        let guard = in_flight.read().await;
+        assert!(registry.remove("acme").is_some());
+        assert!(registry.get("acme").is_none());
+        assert_eq!(guard.node_count().unwrap(), 1);
+        assert!(guard.query("acme module").await.is_ok());
+        drop(guard);
+
+        let handle = {
+            let registry = registry.clone();
+            std::thread::spawn(move || registry.get("globex").is_some())
+        };
+        assert!(handle.join().unwrap());
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reindexing_modified_repository_replaces_nodes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "fn b() {}").unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let mut manual = Node::new("manual".to_string(), NodeType::Module, String::new());
+        manual.repo_id = Some("hand-made".to_string());
+        let manual = engine.add_node(manual).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+        assert_eq!(engine.node_count().unwrap(), 3);
+        let snapshot = engine.snapshot().unwrap();
+
+        std::fs::write(dir.path().join("b.rs"), "// TODO: finish\nfn b() {}").unwrap();
+        std::fs::write(dir.path().join("c.rs"), "fn c() {}").unwrap();
+        engine.index_repository(&repository).await.unwrap();
+
+        assert_eq!(engine.node_count().unwrap(), 4, "a, b, c and the node in another namespace");
+        assert!(engine.get_node(&manual).unwrap().is_some());
+        let delta = engine.diff_against(&snapshot).unwrap();
+        assert_eq!(delta.added_nodes.len(), 1);
+        assert_eq!(delta.changed_nodes.len(), 1);
+        assert!(delta.removed_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_diff_against_matches_nodes_across_reindex() {
+        fn graph(paths: &[&str]) -> (NeuroNodePathEngine, Vec<String>) {
+            let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+            let ids = paths
+                .iter()
+                .map(|path| engine.add_node(Node::new(path.to_string(), NodeType::File, path.to_string())).unwrap())
+                .collect();
+            (engine, ids)
+        }
+
+        let (before, old) = graph(&["a.rs", "b.rs", "c.rs"]);
+        before.add_channel(NeuralChannel::new(old[0].clone(), old[1].clone(), SignalType::DataFlow)).unwrap();
+        let snapshot = before.snapshot().unwrap();
+
+        let (after, new) = graph(&["a.rs", "b.rs", "d.rs"]);
+        let mut edited = after.get_node(&new[1]).unwrap().unwrap();
+        edited.metadata.documentation = Some("Now documented".to_string());
+        after.add_node(edited).unwrap();
+        after.add_channel(NeuralChannel::new(new[0].clone(), new[2].clone(), SignalType::DataFlow)).unwrap();
+
+        let delta = after.diff_against(&snapshot).unwrap();
+        let names = |nodes: &[Node]| nodes.iter().map(|n| n.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&delta.added_nodes), vec!["d.rs"]);
+        assert_eq!(names(&delta.removed_nodes), vec!["c.rs"]);
+        assert_eq!(names(&delta.changed_nodes), vec!["b.rs"]);
+        assert_eq!(delta.added_channels.len(), 1);
+        assert_eq!(delta.added_channels[0].to_node_id, new[2]);
+        assert_eq!(delta.removed_channels.len(), 1);
+        assert_eq!(delta.removed_channels[0].to_node_id, old[1]);
+
+        assert!(after.diff_against(&after.snapshot().unwrap()).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_max_depth_skips_deeper_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("a/b/c")).unwrap();
+        std::fs::write(dir.path().join("root.rs"), "fn r() {}").unwrap();
+        std::fs::write(dir.path().join("a/mid.rs"), "fn m() {}").unwrap();
+        std::fs::write(dir.path().join("a/b/deep.rs"), "fn d() {}").unwrap();
+        std::fs::write(dir.path().join("a/b/c/deeper.rs"), "fn e() {}").unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf())
+            .unwrap()
+            .with_max_depth(2);
+        let report = engine.index_repository(&repository).await.unwrap();
+
+        assert_eq!(report.files_indexed, 2);
+        assert_eq!(report.files_skipped, 2);
+        assert_eq!(engine.node_count().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_debt_markers_recorded_during_indexing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "// TODO: split module\nfn a() {}\n// HACK: skip auth\n").unwrap();
+        std::fs::write(dir.path().join("clean.rs"), "fn b() {}\n").unwrap();
+
+        let config = EngineConfig::builder().debt_markers(["TODO"]).build().unwrap();
+        let mut engine = NeuroNodePathEngine::new(config).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+
+        let markers = engine.debt_markers().unwrap();
+        assert_eq!(markers.len(), 1);
+        let (node_id, marker) = &markers[0];
+        assert!(engine.get_node(node_id).unwrap().unwrap().name.ends_with("lib.rs"));
+        assert_eq!((marker.marker.as_str(), marker.line, marker.text.as_str()), ("TODO", 1, "split module"));
+    }
+
+    #[tokio::test]
+    async fn test_index_progress_reports_reading_and_clustering() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("service")).unwrap();
+        std::fs::write(dir.path().join("service/user.rs"), "fn user() {}").unwrap();
+        std::fs::write(dir.path().join("service/order.rs"), "fn order() {}").unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        let events = std::sync::Mutex::new(Vec::new());
+        engine
+            .index_repository_with_progress(&repository, |p| events.lock().unwrap().push(p))
+            .await
+            .unwrap();
+
+        let events = events.into_inner().unwrap();
+        let reading: Vec<_> = events.iter().filter(|p| p.phase == IndexPhase::Reading).collect();
+        assert_eq!(reading.len(), 2);
+        assert_eq!((reading[1].current, reading[1].total), (2, 2));
+        assert!(events.iter().any(|p| p.phase == IndexPhase::Clustering));
+    }
+
+    #[tokio::test]
+    async fn test_config_files_indexed_and_filtered() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("service")).unwrap();
+        std::fs::write(dir.path().join("service/Cargo.toml"), "[package]").unwrap();
+        std::fs::write(dir.path().join("service/service.rs"), "fn run() {}").unwrap();
+        std::fs::write(dir.path().join("service/service.custom"), "x").unwrap();
+
+        let config = EngineConfig {
+            config_files: ConfigFileSet::default().with_extension(".custom"),
+            ..EngineConfig::default()
+        };
+        let mut engine = NeuroNodePathEngine::new(config).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+
+        let node_types = |ids: &[String]| -> Vec<NodeType> {
+            ids.iter().map(|id| engine.get_node(id).unwrap().unwrap().node_type).collect()
+        };
+        // Indexed nodes are named by path, so the query names every file.
+        let query = ["Cargo.toml", "service.rs", "service.custom"]
+            .map(|f| dir.path().join("service").join(f).to_string_lossy().into_owned())
+            .join(" ");
+        let all = engine.query(&query).await.unwrap();
+        assert_eq!(node_types(&all.node_path).iter().filter(|t| **t == NodeType::Config).count(), 2);
+
+        let filtered = engine.query_filtered(&query, &[NodeType::Config]).await.unwrap();
+        assert_eq!(node_types(&filtered.node_path), vec![NodeType::File]);
+    }
+
+    struct ExtensionClassifier(&'static str, NodeType);
+
+    impl NodeClassifier for ExtensionClassifier {
+        fn classify(&self, path: &str, _content: &str) -> Option<NodeType> {
+            path.ends_with(self.0).then(|| self.1.clone())
+        }
+    }
+
+    struct ContentClassifier;
+
+    impl NodeClassifier for ContentClassifier {
+        fn classify(&self, _path: &str, content: &str) -> Option<NodeType> {
+            content.starts_with("syntax = \"proto3\"").then_some(NodeType::Protocol)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_node_classifiers_run_in_priority_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.tf"), "resource {}").unwrap();
+        std::fs::write(dir.path().join("api.proto"), "syntax = \"proto3\";").unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn lib() {}").unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        engine.register_classifier(0, Box::new(ExtensionClassifier(".tf", NodeType::Module)));
+        engine.register_classifier(
+            10,
+            Box::new(ExtensionClassifier(".tf", NodeType::Custom("terraform".to_string()))),
+        );
+        engine.register_classifier(5, Box::new(ContentClassifier));
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+
+        let expected = [
+            ("main.tf", NodeType::Custom("terraform".to_string())),
+            ("api.proto", NodeType::Protocol),
+            ("lib.rs", NodeType::File),
+        ];
+        for (file, node_type) in expected {
+            let query = dir.path().join(file).to_string_lossy().into_owned();
+            let result = engine.query(&query).await.unwrap();
+            assert_eq!(engine.get_node(&result.node_path[0]).unwrap().unwrap().node_type, node_type);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_rejects_graph_over_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["a.rs", "b.rs", "c.rs"] {
+            std::fs::write(dir.path().join(file), "fn f() {}").unwrap();
+        }
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+
+        let config = EngineConfig {
+            max_nodes: Some(2),
+            ..EngineConfig::default()
+        };
+        let mut engine = NeuroNodePathEngine::new(config).unwrap();
+        let err = engine.index_repository(&repository).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<EngineError>(),
+            Some(&EngineError::GraphTooLarge { kind: "nodes", limit: 2, actual: 3 })
+        );
+        assert_eq!(engine.repository_stats().unwrap().node_count, 0);
+        assert_eq!(engine.repository_stats().unwrap().cluster_count, 0);
+
+        let config = EngineConfig {
+            max_channels: Some(0),
+            ..EngineConfig::default()
+        };
+        let mut engine = NeuroNodePathEngine::new(config).unwrap();
+        let a = add_named_node(&engine, "a");
+        connect(&engine, &a, &a);
+        let err = engine.index_repository(&repository).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<EngineError>(),
+            Some(&EngineError::GraphTooLarge { kind: "channels", limit: 0, actual: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_index_run_adding_too_many_channels_is_discarded() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("b")).unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "mod a;\npub mod b;\nmod missing;\n").unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        std::fs::write(dir.path().join("b/mod.rs"), "fn b() {}").unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+
+        let config = EngineConfig {
+            max_channels: Some(1),
+            ..EngineConfig::default()
+        };
+        let mut engine = NeuroNodePathEngine::new(config).unwrap();
+        let err = engine.index_repository(&repository).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<EngineError>(),
+            Some(&EngineError::GraphTooLarge { kind: "channels", limit: 1, actual: 2 })
+        );
+        let stats = engine.repository_stats().unwrap();
+        assert_eq!((stats.node_count, stats.channel_count, stats.cluster_count), (0, 0, 0));
+
+        let config = EngineConfig {
+            max_channels: Some(2),
+            ..EngineConfig::default()
+        };
+        let mut engine = NeuroNodePathEngine::new(config).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+        let stats = engine.repository_stats().unwrap();
+        assert_eq!(stats.channel_count, 2);
+        assert_eq!(stats.channels_by_signal.get("dependency_link"), Some(&2));
+    }
+
+    #[tokio::test]
+    async fn test_reindex_files_updates_only_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "mod a;\nmod c;\n").unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "fn b() {}").unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let report = engine.reindex_files("web", &repository, &[]).await.unwrap();
+        assert_eq!(report.files_indexed, 3, "an empty namespace is indexed in full");
+        let node_id = |engine: &NeuroNodePathEngine, file: &str| {
+            engine
+                .snapshot().unwrap()
+                .nodes
+                .into_iter()
+                .find(|n| Path::new(&n.name).file_name() == Some(file.as_ref()))
+                .map(|n| n.id)
+        };
+        let a = node_id(&engine, "a.rs").unwrap();
+        assert_eq!(engine.repository_stats().unwrap().channel_count, 1);
+
+        std::fs::write(dir.path().join("a.rs"), "//! Documented\nfn a() {}").unwrap();
+        std::fs::remove_file(dir.path().join("b.rs")).unwrap();
+        std::fs::write(dir.path().join("c.rs"), "fn c() {}").unwrap();
+        let changed = ["a.rs", "b.rs", "c.rs"].map(str::to_string);
+        let report = engine.reindex_files("web", &repository, &changed).await.unwrap();
+
+        assert_eq!(report.files_indexed, 3, "a, c and lib.rs, which declares c");
+        assert_eq!(node_id(&engine, "a.rs"), Some(a.clone()));
+        assert!(engine.get_node(&a).unwrap().unwrap().metadata.documentation.is_some());
+        assert_eq!(node_id(&engine, "b.rs"), None);
+        let stats = engine.repository_stats().unwrap();
+        assert_eq!((stats.node_count, stats.cluster_count), (3, 1));
+        assert_eq!(stats.channels_by_signal.get("dependency_link"), Some(&2));
+        let cluster = engine.clusters_by_type().unwrap()[&ClusterType::Functional][0].clone();
+        assert_eq!(engine.get_cluster(&cluster).unwrap().unwrap().node_ids.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reindex_files_stays_inside_repository_and_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn lib() {}").unwrap();
+        std::fs::create_dir(dir.path().join("a")).unwrap();
+        std::fs::write(dir.path().join("a/deep.rs"), "fn deep() {}").unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf())
+            .unwrap()
+            .with_max_depth(1);
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let report = engine.reindex_files("web", &repository, &[]).await.unwrap();
+        assert_eq!((report.files_indexed, report.files_skipped), (1, 1));
+
+        for escaping in ["../lib.rs", "a/../../lib.rs", "/etc/hostname"] {
+            let changed = [escaping.to_string()];
+            assert!(engine.reindex_files("web", &repository, &changed).await.is_err());
+        }
+        let changed = ["a/deep.rs".to_string()];
+        let report = engine.reindex_files("web", &repository, &changed).await.unwrap();
+        assert_eq!((report.files_indexed, report.files_skipped), (0, 1));
+        assert_eq!(engine.repository_stats().unwrap().node_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_index_and_query() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn lib() {}").unwrap();
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = engine
+            .index_repository_cancellable(&repository, |_| {}, &cancel)
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<EngineError>(), Some(&EngineError::Cancelled));
+
+        let err = engine.query_cancellable("lib", &cancel).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<EngineError>(), Some(&EngineError::Cancelled));
+        assert!(engine.query_cancellable("lib", &CancellationToken::new()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_index_extracts_rust_doc_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("router.rs");
+        std::fs::write(
+            &source,
+            "//! Request routing.\n\n/// Picks the handler\n/// for a path.\npub fn route() {}\n",
+        )
+        .unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+
+        let result = engine.query(&source.to_string_lossy()).await.unwrap();
+        let node = engine.get_node(&result.node_path[0]).unwrap().unwrap();
+        assert_eq!(
+            node.metadata.documentation.as_deref(),
+            Some("Request routing.\n\nPicks the handler\nfor a path.")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_index_discovers_public_interfaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("query.rs");
+        std::fs::write(
+            &source,
+            "pub fn search(term: &str) -> Vec<String> { vec![] }\n\
+             pub trait Validator {\n    fn check(&self) -> bool;\n}\n",
+        )
+        .unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+
+        let result = engine.query(&source.to_string_lossy()).await.unwrap();
+        let interfaces = engine.interfaces_for_node(&result.node_path[0]).unwrap();
+        let summary: Vec<_> = interfaces
+            .iter()
+            .map(|i| (i.name.as_str(), i.interface_type.clone(), i.exposed_methods.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Validator", InterfaceType::ValidationInterface, 1),
+                ("query", InterfaceType::QueryInterface, 1),
+            ]
+        );
+        assert_eq!(result.channel_interfaces.len(), 2);
+        assert!(result.channel_interfaces.iter().all(|id| engine.get_interface(id).unwrap().is_some()));
+    }
+
+    #[test]
+    fn test_clusters_by_type() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let custom = ClusterType::Custom("hot_path".to_string());
+        let a = engine.add_cluster(Cluster::new("a".to_string(), custom.clone())).unwrap();
+        let b = engine.add_cluster(Cluster::new("b".to_string(), custom.clone())).unwrap();
+        let c = engine.add_cluster(Cluster::new("c".to_string(), ClusterType::Semantic)).unwrap();
+
+        let groups = engine.clusters_by_type().unwrap();
+        let mut expected = vec![a, b];
+        expected.sort();
+        assert_eq!(groups[&custom], expected);
+        assert_eq!(groups[&ClusterType::Semantic], vec![c]);
+        assert!(!groups.contains_key(&ClusterType::Functional));
+    }
+
+    #[tokio::test]
+    async fn test_repository_fingerprint_tracks_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "fn lib() {}").unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+
+        let original = repository.fingerprint().await.unwrap();
+        std::fs::write(&file, "fn lib() { todo!() }").unwrap();
+        let touched = repository.fingerprint().await.unwrap();
+        assert_ne!(original, touched);
+        std::fs::write(&file, "fn lib() {}").unwrap();
+        assert_eq!(repository.fingerprint().await.unwrap(), original);
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+        let events = std::sync::Mutex::new(0);
+        engine
+            .index_repository_with_progress(&repository, |_| *events.lock().unwrap() += 1)
+            .await
+            .unwrap();
+        assert_eq!(*events.lock().unwrap(), 0, "unchanged repository is not re-indexed");
+    }
+
+    #[tokio::test]
+    async fn test_non_utf8_files_are_indexed_lossily() {
+        let dir = tempfile::tempdir().unwrap();
+        let blob = dir.path().join("blob.rs");
+        std::fs::write(&blob, b"pub fn survives() {}\n\xff\xfe\x00").unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn lib() {}").unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        let report = engine.index_repository(&repository).await.unwrap();
+
+        assert_eq!(report.files_indexed, 2);
+        assert_eq!(report.lossy_files, vec![blob.to_string_lossy().into_owned()]);
+        assert!(report.unreadable_files.is_empty());
+        let result = engine.query(&blob.to_string_lossy()).await.unwrap();
+        assert_eq!(engine.interfaces_for_node(&result.node_path[0]).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_structured_query_filters_nodes() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let add = |name: &str, node_type: NodeType, language: &str| {
+            let mut node = Node::new(name.to_string(), node_type, format!("src/{}", name));
+            node.metadata.language = language.to_string();
+            engine.add_node(node).unwrap()
+        };
+        let get_user = add("get_user", NodeType::Function, "rust");
+        let user_struct = add("user", NodeType::Struct, "rust");
+        let py_user = add("load_user", NodeType::Function, "python");
+        let delete_user = add("delete_user", NodeType::Function, "rust");
+
+        let mut service = Cluster::new("service".to_string(), ClusterType::Functional);
+        for id in [&get_user, &user_struct, &py_user] {
+            service.add_node(id.clone());
+        }
+        engine.add_cluster(service).unwrap();
+
+        let result = engine
+            .query_structured("type:function lang:rust cluster:service name~user")
+            .await
+            .unwrap();
+        assert_eq!(result.node_path, vec![get_user.clone()]);
+
+        let result = engine.query_structured("type:function lang:rust delete_user").await.unwrap();
+        assert_eq!(result.node_path, vec![delete_user]);
+        assert!(engine.query_structured("owner:me").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_pagination_keeps_totals_and_full_audit() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let root = add_named_node(&engine, "root");
+        let children: Vec<String> = (0..5)
+            .map(|i| {
+                let mut child = Node::new(format!("child{}", i), NodeType::Function, String::new());
+                child.parent_id = Some(root.clone());
+                engine.add_node(child).unwrap()
+            })
+            .collect();
+
+        let full = engine.query("root").await.unwrap();
+        let page = engine.query_paged("root", 2, 2).await.unwrap();
+        assert_eq!(page.total_nodes, 6);
+        assert_eq!(page.node_path.len(), 2);
+        assert_eq!(page.node_path, full.node_path[2..4].to_vec());
+        assert!(page.node_path.iter().all(|id| children.contains(id)));
+
+        let entries = engine.audit_entries();
+        assert_eq!(entries.last().unwrap().node_path.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_diff_results_ignores_run_specific_fields() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let root = add_named_node(&engine, "root");
+
+        let first = engine.query("root").await.unwrap();
+        let second = engine.query("root").await.unwrap();
+        assert_ne!(first.request_id, second.request_id);
+        let diff = diff_results(&first, &second);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "no changes\n");
+
+        let mut child = Node::new("child".to_string(), NodeType::Function, String::new());
+        child.parent_id = Some(root);
+        let child = engine.add_node(child).unwrap();
+        let third = engine.query("root").await.unwrap();
+        let diff = diff_results(&second, &third);
+        assert_eq!(diff.nodes_inserted, vec![child.clone()]);
+        assert!(diff.nodes_removed.is_empty());
+        assert!(diff.to_string().contains(&format!("+ node {}", child)));
+
+        let snapshot: QueryResult = serde_json::from_str(&serde_json::to_string(&third).unwrap()).unwrap();
+        assert!(diff_results(&snapshot, &third).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_normalized_names_match_across_spellings() {
+        let config = EngineConfig {
+            normalize_names: true,
+            ..EngineConfig::default()
+        };
+        let engine = NeuroNodePathEngine::new(config).unwrap();
+        let mut expected: Vec<String> = ["getUserName", "get_user_name", "GetUserName"]
+            .iter()
+            .map(|name| add_named_node(&engine, name))
+            .collect();
+        add_named_node(&engine, "username");
+        add_named_node(&engine, "getOrders");
+        expected.sort();
+
+        assert_eq!(engine.get_node(&expected[0]).unwrap().unwrap().normalized_name, "get user name");
+        let result = engine.query("user name").await.unwrap();
+        let mut found = result.node_path.clone();
+        found.sort();
+        assert_eq!(found, expected);
+        assert!(engine
+            .explain_path(&result).unwrap()
+            .iter()
+            .all(|step| matches!(step.reason, StepReason::MatchedQuery { .. })));
+
+        let plain = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        add_named_node(&plain, "getUserName");
+        assert!(plain.query("user name").await.unwrap().node_path.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_follows_children_to_configured_max_depth() {
+        let config = EngineConfig::builder().max_depth(1).build().unwrap();
+        let engine = NeuroNodePathEngine::new(config).unwrap();
+        let mut parent_id = None;
+        let mut ids = Vec::new();
+        for name in ["service", "handler", "helper"] {
+            let mut node = Node::new(name.to_string(), NodeType::Module, format!("src/{}", name));
+            node.parent_id = parent_id.take();
+            parent_id = Some(node.id.clone());
+            ids.push(engine.add_node(node).unwrap());
+        }
+
+        let result = engine.query("find the service").await.unwrap();
+        assert_eq!(result.node_path, ids[..2].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_query_deadline_returns_partial_path() {
+        let config = EngineConfig {
+            query_timeout: Some(std::time::Duration::ZERO),
+            ..EngineConfig::default()
+        };
+        let engine = NeuroNodePathEngine::new(config).unwrap();
+        add_named_node(&engine, "root");
+
+        let result = engine.query("root").await.unwrap();
+        assert!(result.partial);
+        assert!(result.node_path.is_empty());
+        let reason = result.partial_reason.unwrap();
+        assert_eq!(engine.audit_entries().last().unwrap().truncation, Some(reason));
+
+        let unbounded = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        add_named_node(&unbounded, "root");
+        let result = unbounded.query("root").await.unwrap();
+        assert!(!result.partial);
+        assert_eq!(result.node_path.len(), 1);
+        assert_eq!(unbounded.audit_entries().last().unwrap().truncation, None);
+    }
+
+    #[tokio::test]
+    async fn test_repository_namespaces() {
+        let service = tempfile::tempdir().unwrap();
+        let shared = tempfile::tempdir().unwrap();
+        for dir in [&service, &shared] {
+            std::fs::create_dir(dir.path().join("src")).unwrap();
+            std::fs::write(dir.path().join("src/lib.rs"), "fn lib() {}").unwrap();
+        }
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let service_repo = CodeRepository::new(service.path().to_path_buf()).unwrap();
+        let shared_repo = CodeRepository::new(shared.path().to_path_buf()).unwrap();
+        engine.index_repository_as("service", &service_repo).await.unwrap();
+        engine.index_repository_as("shared", &shared_repo).await.unwrap();
+        assert_eq!(engine.repositories().unwrap(), vec!["service".to_string(), "shared".to_string()]);
+
+        let service_nodes = engine.query_structured("repo:service").await.unwrap().node_path;
+        let shared_nodes = engine.query_structured("repo:shared name~lib").await.unwrap().node_path;
+        assert_eq!((service_nodes.len(), shared_nodes.len()), (1, 1));
+        let clusters: Vec<_> = engine.clusters_by_type().unwrap()[&ClusterType::Functional]
+            .iter()
+            .filter_map(|id| engine.get_cluster(id).unwrap().unwrap().repo_id)
+            .collect();
+        assert_eq!(clusters.len(), 2);
+
+        let link = connect(&engine, &service_nodes[0], &shared_nodes[0]);
+        assert_eq!(engine.cross_repo_channels().unwrap(), vec![link]);
+
+        std::fs::write(service.path().join("src/main.rs"), "fn main() {}").unwrap();
+        engine.index_repository_as("service", &service_repo).await.unwrap();
+        let service_nodes = engine.query_structured("repo:service").await.unwrap().node_path;
+        assert_eq!(service_nodes.len(), 2, "re-index replaces the namespace");
+        assert!(engine.cross_repo_channels().unwrap().is_empty());
+        assert_eq!(engine.query_structured("repo:shared").await.unwrap().node_path, shared_nodes);
+    }
+
+    type EventLog = std::sync::Arc<std::sync::Mutex<Vec<String>>>;
+
+    struct RecordingObserver(EventLog);
+
+    impl RecordingObserver {
+        fn record(&self, event: String) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    impl EngineObserver for RecordingObserver {
+        fn on_node_indexed(&self, _node: &Node) {
+            self.record("node".to_string());
+        }
+
+        fn on_cluster_formed(&self, cluster: &Cluster) {
+            self.record(format!("cluster:{}", cluster.name));
+        }
+
+        fn on_query_executed(&self, _result: &QueryResult) {
+            self.record("query".to_string());
+        }
+
+        fn on_validation_failed(&self, _result: &QueryResult) {
+            self.record("invalid".to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_observers_receive_engine_events() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("service")).unwrap();
+        std::fs::write(dir.path().join("service/user.rs"), "fn user() {}").unwrap();
+
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let logs = [EventLog::default(), EventLog::default()];
+        for log in &logs {
+            engine.add_observer(Box::new(RecordingObserver(log.clone())));
+        }
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+        engine.query("matches nothing").await.unwrap();
+
+        for log in &logs {
+            assert_eq!(
+                *log.lock().unwrap(),
+                vec!["node", "cluster:service", "query", "invalid"]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_node_tags_query_and_serialize() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let hot = add_named_node(&engine, "parse_request");
+        let legacy = add_named_node(&engine, "parse_legacy");
+        assert!(engine.tag_node(&hot, "hotpath").unwrap());
+        assert!(engine.tag_node(&legacy, "hotpath").unwrap());
+        assert!(engine.tag_node(&legacy, "legacy").unwrap());
+        assert!(!engine.tag_node("missing", "hotpath").unwrap());
+
+        let result = engine.query_structured("tag:hotpath tag:legacy").await.unwrap();
+        assert_eq!(result.node_path, vec![legacy.clone()]);
+
+        assert!(engine.untag_node(&legacy, "hotpath").unwrap());
+        assert!(!engine.untag_node(&legacy, "hotpath").unwrap());
+        assert_eq!(engine.nodes_with_tag("hotpath").unwrap(), vec![hot.clone()]);
+
+        let json = serde_json::to_string(&engine.get_node(&legacy).unwrap().unwrap()).unwrap();
+        let restored: Node = serde_json::from_str(&json).unwrap();
+        assert!(restored.metadata.tags.contains("legacy"));
+    }
+
+    #[tokio::test]
+    async fn test_seeded_engines_produce_identical_results() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("service")).unwrap();
+        for file in ["user.rs", "order.rs", "billing.rs"] {
+            std::fs::write(dir.path().join("service").join(file), "pub fn run() {}").unwrap();
+        }
+        let repository = CodeRepository::new(dir.path().to_path_buf()).unwrap();
+        let query = format!("service {}", dir.path().join("service/user.rs").to_string_lossy());
+
+        let mut outputs = Vec::new();
+        for _ in 0..2 {
+            let config = EngineConfig {
+                seed: Some(42),
+                ..EngineConfig::default()
+            };
+            let mut engine = NeuroNodePathEngine::new(config).unwrap();
+            engine.index_repository(&repository).await.unwrap();
+            let result = engine.query(&query).await.unwrap();
+            assert!(!result.node_path.is_empty());
+            outputs.push(serde_json::to_value(&result).unwrap());
+        }
+        assert_eq!(outputs[0], outputs[1]);
+    }
+
+    #[test]
+    fn test_simulate_tick_propagates_through_active_channels() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let source = engine.add_neuron(Neuron::new("source".to_string(), "input".to_string(), 1.0));
+        let target = engine.add_neuron(Neuron::new("target".to_string(), "hidden".to_string(), 1.0));
+        let idle = engine.add_neuron(Neuron::new("idle".to_string(), "hidden".to_string(), 1.0));
+
+        let mut channel = NeuralChannel::new(source.clone(), target.clone(), SignalType::DataFlow);
+        channel.set_weight(0.5);
+        let channel_id = engine.add_channel(channel).unwrap();
+        let mut muted = NeuralChannel::new(source.clone(), idle.clone(), SignalType::DataFlow);
+        muted.deactivate();
+        engine.add_channel(muted).unwrap();
+
+        let outputs = engine.simulate_tick(HashMap::from([(source.clone(), 1.0)])).unwrap();
+
+        assert_eq!(outputs.len(), 3);
+        assert!(outputs[&source] > outputs[&target]);
+        assert!(outputs[&target] > 0.5);
+        assert_eq!(outputs[&idle], 0.5);
+        assert_eq!(engine.get_channel(&channel_id).unwrap().unwrap().metadata.signal_count, 1);
+    }
+
+    #[test]
+    fn test_repository_stats() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let root = add_named_node(&engine, "root");
+        let mut child = Node::new("child".to_string(), NodeType::Function, String::new());
+        child.parent_id = Some(root.clone());
+        let child = engine.add_node(child).unwrap();
+        let mut grandchild = Node::new("grandchild".to_string(), NodeType::Function, String::new());
+        grandchild.parent_id = Some(child.clone());
+        engine.add_node(grandchild).unwrap();
+        let linked = add_named_node(&engine, "linked");
+        add_named_node(&engine, "orphan");
+        connect(&engine, &root, &linked);
+        let mut cluster = Cluster::new("core".to_string(), ClusterType::Functional);
+        cluster.cohesion_score = 0.5;
+        engine.add_cluster(cluster).unwrap();
+
+        let stats = engine.repository_stats().unwrap();
+        assert_eq!(stats.node_count, 5);
+        assert_eq!(stats.nodes_by_type["module"], 3);
+        assert_eq!(stats.nodes_by_type["function"], 2);
+        assert_eq!(stats.channel_count, 1);
+        assert_eq!(stats.channels_by_signal["call_graph"], 1);
+        assert_eq!(stats.cluster_count, 1);
+        assert_eq!(stats.average_cohesion, 0.5);
+        assert_eq!(stats.orphan_count, 1);
+        assert_eq!(stats.max_depth, 2);
+    }
+
+    #[test]
+    fn test_health_score_components() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        assert_eq!(engine.health_score().unwrap().value, 100.0);
+
+        let a = add_named_node(&engine, "a");
+        let b = add_named_node(&engine, "b");
+        let c = add_named_node(&engine, "c");
+        add_named_node(&engine, "orphan");
+        engine.add_channel(NeuralChannel::new(a.clone(), b.clone(), SignalType::DependencyLink)).unwrap();
+        engine.add_channel(NeuralChannel::new(b.clone(), a.clone(), SignalType::DependencyLink)).unwrap();
+        connect(&engine, &b, &c);
+        let mut cluster = Cluster::new("core".to_string(), ClusterType::Functional);
+        cluster.cohesion_score = 0.5;
+        engine.add_cluster(cluster).unwrap();
+
+        let health = engine.health_score().unwrap();
+        assert_eq!(health.components["cohesion"], 50.0);
+        assert_eq!(health.components["orphans"], 75.0);
+        assert_eq!(health.components["cycles"], 50.0);
+        assert_eq!(health.components["layering"], 100.0);
+        assert!((health.value - 65.0).abs() < 1e-9);
+
+        let layered = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let top = add_named_node(&layered, "top");
+        let bottom = add_named_node(&layered, "bottom");
+        connect(&layered, &top, &bottom);
+        let health = layered.health_score_with(&[LayerRule::forbid("layer_0", "layer_1")]).unwrap();
+        assert_eq!(health.components["layering"], 50.0);
+    }
+
+    #[test]
+    fn test_god_objects_by_degree_z_score() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let manager = add_named_node(&engine, "manager");
+        for i in 0..9 {
+            let leaf = add_named_node(&engine, &format!("leaf{}", i));
+            if i % 2 == 0 {
+                connect(&engine, &leaf, &manager);
+            } else {
+                connect(&engine, &manager, &leaf);
+            }
+        }
+
+        let smells = engine.god_objects(2.5).unwrap();
+        assert_eq!(smells.len(), 1);
+        assert_eq!(smells[0].node_id, manager);
+        assert_eq!(smells[0].kind, SmellKind::GodObject);
+        assert_eq!((smells[0].degree.fan_in, smells[0].degree.fan_out), (5, 4));
+        assert_eq!(smells[0].threshold, 2.5);
+        assert!((smells[0].z_score - 3.0).abs() < 1e-9);
+
+        assert!(engine.god_objects(3.5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_node_neighborhood_by_radius() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let a = add_named_node(&engine, "a");
+        let b = add_named_node(&engine, "b");
+        let c = add_named_node(&engine, "c");
+        let ab = connect(&engine, &a, &b);
+        let cb = connect(&engine, &c, &b);
+
+        let alone = engine.neighborhood(&a, 0).unwrap().unwrap();
+        assert_eq!(alone.node.id, a);
+        assert!(alone.neighbors.is_empty() && alone.channels.is_empty());
+
+        let near = engine.neighborhood(&a, 1).unwrap().unwrap();
+        let ids: Vec<_> = near.neighbors.iter().map(|n| n.id.clone()).collect();
+        assert_eq!(ids, vec![b.clone()]);
+        assert_eq!(near.channels.iter().map(|ch| ch.id.clone()).collect::<Vec<_>>(), vec![ab.clone()]);
+
+        let wide = engine.neighborhood(&a, 2).unwrap().unwrap();
+        assert_eq!(wide.neighbors.len(), 2);
+        let mut channel_ids = vec![ab, cb];
+        channel_ids.sort();
+        assert_eq!(wide.channels.iter().map(|ch| ch.id.clone()).collect::<Vec<_>>(), channel_ids);
+
+        assert!(engine.neighborhood("missing", 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_force_directed_layout() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let a = add_named_node(&engine, "a");
+        let b = add_named_node(&engine, "b");
+        let c = add_named_node(&engine, "c");
+        let d = add_named_node(&engine, "d");
+        connect(&engine, &a, &b);
+        connect(&engine, &c, &d);
+
+        let layout = engine.compute_layout(LayoutAlgorithm::ForceDirected2D { iterations: 200 }).unwrap();
+        assert_eq!(layout.len(), 4);
+        assert!(layout.values().all(|p| p[2] == 0.0));
+        assert_eq!(engine.get_node(&a).unwrap().unwrap().position, Some(layout[&a]));
+        assert_eq!(
+            engine.compute_layout(LayoutAlgorithm::ForceDirected2D { iterations: 200 }).unwrap(),
+            layout
+        );
+
+        let distance = |x: &str, y: &str| {
+            let (p, q) = (layout[x], layout[y]);
+            ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt()
+        };
+        assert!(distance(&a, &b) < distance(&a, &c));
+        assert!(distance(&c, &d) < distance(&b, &d));
+
+        let spatial = engine.compute_layout(LayoutAlgorithm::ForceDirected3D { iterations: 50 }).unwrap();
+        assert!(spatial.values().any(|p| p[2] != 0.0));
+    }
+
+    fn add_named_node(engine: &NeuroNodePathEngine, name: &str) -> String {
+        engine.add_node(Node::new(name.to_string(), NodeType::Module, format!("src/{}.rs", name))).unwrap()
+    }
+
+    fn connect(engine: &NeuroNodePathEngine, from: &str, to: &str) -> String {
+        engine.add_channel(NeuralChannel::new(from.to_string(), to.to_string(), SignalType::CallGraph)).unwrap()
+    }
+
+    #[test]
+    fn test_transmit_along_path() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let a = add_named_node(&engine, "a");
+        let b = add_named_node(&engine, "b");
+        let c = add_named_node(&engine, "c");
+        let mut first = NeuralChannel::new(a.clone(), b.clone(), SignalType::CallGraph);
+        first.set_weight(0.5);
+        let first = engine.add_channel(first).unwrap();
+        let mut second = NeuralChannel::new(b.clone(), c.clone(), SignalType::CallGraph);
+        second.set_weight(0.4);
+        let second = engine.add_channel(second).unwrap();
+
+        let hops = engine.transmit_along(&[a.clone(), b.clone(), c.clone()], 10.0).unwrap();
+        assert_eq!(hops, vec![(first.clone(), 5.0), (second, 2.0)]);
+        assert_eq!(engine.get_channel(&first).unwrap().unwrap().metadata.signal_count, 1);
+
+        let error = engine.transmit_along(&[a.clone(), b.clone(), a.clone()], 10.0).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<EngineError>(),
+            Some(&EngineError::MissingChannel { from: b, to: a })
+        );
+        assert_eq!(engine.get_channel(&first).unwrap().unwrap().metadata.signal_count, 1);
+    }
+
+    #[test]
+    fn test_cluster_max_flow() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let ids: Vec<String> = ["a1", "a2", "mid", "b1", "b2"]
+            .iter()
+            .map(|name| add_named_node(&engine, name))
+            .collect();
+        let link = |from: usize, to: usize, bandwidth: f64| {
+            let mut channel = NeuralChannel::new(ids[from].clone(), ids[to].clone(), SignalType::CallGraph);
+            channel.bandwidth = bandwidth;
+            engine.add_channel(channel).unwrap();
+        };
+        link(0, 3, 10.0);
+        link(1, 2, 30.0);
+        link(2, 4, 5.0);
+        link(0, 1, 100.0);
+        link(4, 0, 50.0);
+
+        let mut a = Cluster::new("a".to_string(), ClusterType::Functional);
+        a.add_node(ids[0].clone());
+        a.add_node(ids[1].clone());
+        let mut b = Cluster::new("b".to_string(), ClusterType::Functional);
+        b.add_node(ids[3].clone());
+        b.add_node(ids[4].clone());
+        let (a, b) = (engine.add_cluster(a).unwrap(), engine.add_cluster(b).unwrap());
+
+        assert_eq!(engine.cluster_max_flow(&a, &b).unwrap(), 15.0);
+        assert_eq!(engine.cluster_max_flow(&b, &a).unwrap(), 50.0);
+        let empty = engine.add_cluster(Cluster::new("empty".to_string(), ClusterType::Functional)).unwrap();
+        assert_eq!(engine.cluster_max_flow(&a, &empty).unwrap(), 0.0);
+        assert_eq!(engine.cluster_max_flow(&a, "missing").unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_graph_export_formats() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let from = engine.add_node(Node::new("say \"hi\" <now>".to_string(), NodeType::Function, String::new())).unwrap();
+        let to = add_named_node(&engine, "target");
+        let channel = connect(&engine, &from, &to);
+
+        let dot = engine.export_graph("DOT".parse().unwrap()).unwrap();
+        assert!(dot.starts_with("digraph neuro_node_path {\n"));
+        assert!(dot.contains(&format!("\"{}\" [label=\"say \\\"hi\\\" <now>\", type=\"function\"];", from)));
+        assert!(dot.contains(&format!("\"{}\" -> \"{}\" [id=\"{}\", signal=\"call_graph\", weight=1];", from, to, channel)));
+
+        let graphml = engine.export_graph(GraphFormat::GraphMl).unwrap();
+        assert!(graphml.contains("<data key=\"name\">say &quot;hi&quot; &lt;now&gt;</data>"));
+        assert!(graphml.contains(&format!("<edge id=\"{}\" source=\"{}\" target=\"{}\">", channel, from, to)));
+
+        let json: serde_json::Value = serde_json::from_str(&engine.export_graph(GraphFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 2);
+        assert_eq!(json["channels"][0]["id"], channel.as_str());
+
+        let snapshot = engine.snapshot().unwrap();
+        add_named_node(&engine, "added later");
+        let mut from_snapshot = Vec::new();
+        snapshot.write_graph(GraphFormat::Dot, &mut from_snapshot).unwrap();
+        assert_eq!(String::from_utf8(from_snapshot).unwrap(), dot);
+
+        assert!("svg".parse::<GraphFormat>().is_err());
+        assert_eq!(GraphFormat::GraphMl.content_type(), "application/graphml+xml");
+    }
+
+    #[test]
+    fn test_signal_weights_choose_weighted_path() {
+        let route = |call_graph: f64, dependency: f64| {
+            let config = EngineConfig {
+                signal_weights: HashMap::from([
+                    (SignalType::CallGraph, call_graph),
+                    (SignalType::DependencyLink, dependency),
+                ]),
+                ..EngineConfig::default()
+            };
+            let engine = NeuroNodePathEngine::new(config).unwrap();
+            let names = ["start", "via_calls", "via_deps", "end"];
+            let ids: Vec<String> = names.iter().map(|n| add_named_node(&engine, n)).collect();
+            for (from, to, signal) in [
+                (0, 1, SignalType::CallGraph),
+                (1, 3, SignalType::CallGraph),
+                (0, 2, SignalType::DependencyLink),
+                (2, 3, SignalType::DependencyLink),
+            ] {
+                engine.add_channel(NeuralChannel::new(ids[from].clone(), ids[to].clone(), signal)).unwrap();
+            }
+            let path = engine.weighted_path(&ids[0], &ids[3]).unwrap().unwrap();
+            assert_eq!(path.channel_ids.len(), 2);
+            names[ids.iter().position(|id| *id == path.node_ids[1]).unwrap()]
+        };
+
+        assert_eq!(route(4.0, 1.0), "via_calls");
+        assert_eq!(route(1.0, 4.0), "via_deps");
+    }
+
+    #[test]
+    fn test_feedback_loops_separate_recurrence_from_dependency_cycles() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let [input, reservoir, readout, autapse, module_a, module_b] =
+            ["input", "reservoir", "readout", "autapse", "module_a", "module_b"]
+                .map(|n| add_named_node(&engine, n));
+        let link = |from: &String, to: &String, signal: SignalType| {
+            engine.add_channel(NeuralChannel::new(from.clone(), to.clone(), signal)).unwrap();
+        };
+        link(&input, &reservoir, SignalType::DataFlow);
+        link(&reservoir, &readout, SignalType::DataFlow);
+        link(&readout, &reservoir, SignalType::ControlFlow);
+        link(&autapse, &autapse, SignalType::DataFlow);
+        link(&module_a, &module_b, SignalType::DependencyLink);
+        link(&module_b, &module_a, SignalType::DependencyLink);
+
+        let mut loops: Vec<Vec<String>> = engine
+            .feedback_loops().unwrap()
+            .into_iter()
+            .map(|mut cycle| {
+                cycle.sort();
+                cycle
+            })
+            .collect();
+        loops.sort_by_key(|cycle| cycle.len());
+        let mut recurrent = vec![reservoir.clone(), readout.clone()];
+        recurrent.sort();
+        assert_eq!(loops, vec![vec![autapse.clone()], recurrent]);
+
+        let dependency = engine.dependency_cycles().unwrap();
+        assert_eq!(dependency.len(), 1);
+        assert_eq!(dependency[0].len(), 2);
+        assert!(dependency[0].contains(&module_a) && dependency[0].contains(&module_b));
+    }
+
+    #[test]
+    fn test_infer_layers_uses_longest_path_and_breaks_cycles() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let api = add_named_node(&engine, "api");
+        let service = add_named_node(&engine, "service");
+        let data = add_named_node(&engine, "data");
+        connect(&engine, &api, &service);
+        connect(&engine, &service, &data);
+        connect(&engine, &api, &data);
+
+        let inference = engine.infer_layers().unwrap();
+        assert!(inference.broken_channels.is_empty());
+        let layer_nodes: Vec<_> = inference.layers.iter().map(|l| l.node_ids.clone()).collect();
+        assert_eq!(layer_nodes, vec![vec![api.clone()], vec![service.clone()], vec![data.clone()]]);
+
+        let cluster_ids = engine.register_layer_clusters(&inference.layers).unwrap();
+        assert_eq!(cluster_ids.len(), 3);
+    }
+
+    #[test]
+    fn test_infer_layers_reports_broken_cycles() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let left = add_named_node(&engine, "left");
+        let right = add_named_node(&engine, "right");
+        let forward = connect(&engine, &left, &right);
+        let backward = connect(&engine, &right, &left);
+
+        let inference = engine.infer_layers().unwrap();
+        assert_eq!(inference.broken_channels.len(), 1);
+        assert!(inference.broken_channels[0] == forward || inference.broken_channels[0] == backward);
+        assert_eq!(inference.layers.len(), 2);
+    }
+
+    #[test]
+    fn test_layer_rule_violations() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let api = add_named_node(&engine, "api");
+        let data = add_named_node(&engine, "data");
+        connect(&engine, &api, &data);
+
+        let mut layers = engine.infer_layers().unwrap().layers;
+        layers[0].name = "api".to_string();
+        layers[1].name = "data".to_string();
+        let rules = vec![LayerRule::forbid("data", "api")];
+        assert!(engine.check_layer_rules_with(&layers, &rules).unwrap().is_empty());
+
+        let upward = connect(&engine, &data, &api);
+        let violations = engine.check_layer_rules_with(&layers, &rules).unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].channel_id, upward);
+        assert_eq!(violations[0].from_node_id, data);
+        assert_eq!(violations[0].to_node_id, api);
+    }
+
+    #[tokio::test]
+    async fn test_explain_path_justifies_each_hop() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let parent = Node::new("service".to_string(), NodeType::Module, "src/service".to_string());
+        let parent_id = parent.id.clone();
+        let mut child = Node::new("handler".to_string(), NodeType::Function, "src/service/handler.rs".to_string());
+        child.parent_id = Some(parent_id.clone());
+        engine.add_node(parent).unwrap();
+        let child_id = engine.add_node(child).unwrap();
+        let channel_id = connect(&engine, &parent_id, &child_id);
+
+        let result = engine.query("find the service").await.unwrap();
+        assert_eq!(result.node_path, vec![parent_id.clone(), child_id.clone()]);
+
+        let steps = engine.explain_path(&result).unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].reason, StepReason::MatchedQuery { term: "service".to_string() });
+        assert_eq!(steps[1].reason, StepReason::FollowedParent { parent_id: parent_id.clone() });
+        assert_eq!(steps[1].channel_used, Some(channel_id));
+        assert!(steps[1].reason_de.contains("Kindknoten"));
+
+        let result = engine.query_structured("type:module").await.unwrap();
+        assert_eq!(result.node_path, vec![parent_id]);
+        let steps = engine.explain_path(&result).unwrap();
+        assert_eq!(steps[0].reason, StepReason::MatchedQuery { term: "service".to_string() });
+    }
+
+    #[tokio::test]
+    async fn test_audit_subscribers_receive_entries_and_count_drops() {
+        let engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let mut subscription = engine.subscribe_audit();
+        engine.query("first").await.unwrap();
+        assert_eq!(subscription.recv().await.unwrap().query, "first");
+        assert_eq!(subscription.dropped(), 0);
+
+        let mut trail = AuditTrail::with_subscriber_capacity(2);
+        let mut slow = AuditSubscription::new(trail.subscribe());
+        for query in ["a", "b", "c", "d", "e"] {
+            trail.log_query(&QueryContext::new(query), &[], &[]).unwrap();
+        }
+        assert_eq!(slow.recv().await.unwrap().query, "d");
+        assert_eq!(slow.dropped(), 3);
+        assert_eq!(trail.get_entries().len(), 5);
+    }
+
+    #[test]
+    fn test_signed_audit_entries() {
+        let context = QueryContext {
+            request_id: "req-1".to_string(),
+            ..QueryContext::new("neural path")
+        };
+        let first_key = SigningKey::from_bytes(&[7u8; 32]);
+        let second_key = SigningKey::from_bytes(&[9u8; 32]);
+
+        let mut trail = AuditTrail::new();
+        trail.set_signing_key(first_key.clone(), None);
+        trail.log_query(&context, &["a".to_string()], &[]).unwrap();
+        trail.set_signing_key(second_key.clone(), Some("rotated 2026-10".to_string()));
+        trail.log_query(&context, &["b".to_string()], &[]).unwrap();
+
+        assert!(trail.verify_integrity());
+        assert_eq!(trail.get_entries()[1].key_note.as_deref(), Some("rotated 2026-10"));
+        let trusted = [first_key.verifying_key(), second_key.verifying_key()];
+        assert!(trail.verify_authenticity(&trusted));
+        assert!(!trail.verify_authenticity(&trusted[..1]));
+
+        let entries = trail.get_entries().to_vec();
+        assert!(audit_trail::verify_entries(&entries, true));
+        let mut edited = entries.clone();
+        edited[0].query = "edited".to_string();
+        assert!(!audit_trail::verify_entries(&edited, false));
+        let mut unsigned = entries.clone();
+        unsigned[1].signature = None;
+        assert!(!audit_trail::verify_entries(&unsigned, false));
+
+        let mut plain = AuditTrail::new();
+        plain.log_query(&context, &[], &[]).unwrap();
+        let unsigned = plain.get_entries().to_vec();
+        assert!(audit_trail::verify_entries(&unsigned, false));
+        assert!(!audit_trail::verify_entries(&unsigned, true));
+    }
+
+    #[tokio::test]
+    async fn test_query_in_language() {
+        let config = EngineConfig {
+            supported_languages: vec!["de".to_string(), "en".to_string(), "es".to_string()],
+            ..EngineConfig::default()
+        };
+        let engine = NeuroNodePathEngine::new(config).unwrap();
+
+        let german = engine.query_in("find node", "de").await.unwrap();
+        assert_eq!(german.language, "de");
+        assert_eq!(german.explanation, german.explanation_de);
+        assert_eq!(german.language_note, None);
+
+        let spanish = engine.query_in("find node", "es").await.unwrap();
+        assert_eq!(spanish.language, "es");
+        assert_eq!(spanish.explanation, spanish.explanation_en);
+        assert!(spanish.language_note.unwrap().contains("'es'"));
+
+        let french = engine.query_in("find node", "fr").await.unwrap();
+        assert_eq!(french.language, "de");
+        assert_eq!(french.explanation, french.explanation_de);
+        assert_eq!(
+            french.language_note.as_deref(),
+            Some("Language 'fr' is not supported; answered in 'de'")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_engine_over_shared_graph_store() {
+        let store = Arc::new(InMemoryGraphStore::new());
+        let preloaded = Node::new("UserService".to_string(), NodeType::Module, "src/user.rs".to_string());
+        store.nodes().insert(preloaded.id.clone(), preloaded.clone()).unwrap();
+
+        let engine = NeuroNodePathEngine::with_store(EngineConfig::default(), store.clone()).unwrap();
+        let result = engine.query("find UserService").await.unwrap();
+        assert_eq!(result.node_path, vec![preloaded.id.clone()]);
+
+        let added = engine.add_node(Node::new("Repo".to_string(), NodeType::Module, "src/repo.rs".to_string())).unwrap();
+        assert!(store.nodes().contains(&added).unwrap());
+        assert!(engine.tag_node(&preloaded.id, "core").unwrap());
+        assert!(store.nodes().get(&preloaded.id).unwrap().unwrap().metadata.tags.contains("core"));
+    }
+
+    /// A backend that is always down.
+    struct UnavailableTable;
+
+    impl<T: Clone> Table<T> for UnavailableTable {
+        fn insert(&self, _: String, _: T) -> Result<(), EngineError> { Err(unavailable()) }
+        fn get(&self, _: &str) -> Result<Option<T>, EngineError> { Err(unavailable()) }
+        fn contains(&self, _: &str) -> Result<bool, EngineError> { Err(unavailable()) }
+        fn update(&self, _: &str, _: &mut dyn FnMut(&mut T)) -> Result<bool, EngineError> { Err(unavailable()) }
+        fn remove(&self, _: &str) -> Result<Option<T>, EngineError> { Err(unavailable()) }
+        fn len(&self) -> Result<usize, EngineError> { Err(unavailable()) }
+        fn for_each(&self, _: &mut dyn FnMut(&T)) -> Result<(), EngineError> { Err(unavailable()) }
+        fn retain(&self, _: &mut dyn FnMut(&T) -> bool) -> Result<(), EngineError> { Err(unavailable()) }
+    }
+
+    fn unavailable() -> EngineError {
+        EngineError::Store { message: "connection refused".to_string() }
+    }
+
+    struct UnavailableStore;
+
+    impl GraphStore for UnavailableStore {
+        fn nodes(&self) -> &dyn Table<Node> { &UnavailableTable }
+        fn clusters(&self) -> &dyn Table<Cluster> { &UnavailableTable }
+        fn channels(&self) -> &dyn Table<NeuralChannel> { &UnavailableTable }
+        fn interfaces(&self) -> &dyn Table<neuro_node_path_engine::Interface> { &UnavailableTable }
+    }
+
+    #[tokio::test]
+    async fn test_store_failures_reach_the_caller() {
+        let engine = NeuroNodePathEngine::with_store(EngineConfig::default(), Arc::new(UnavailableStore)).unwrap();
+        let node = Node::new("Repo".to_string(), NodeType::Module, "src/repo.rs".to_string());
+        let error = engine.add_node(node).unwrap_err();
+        assert!(matches!(error.downcast_ref::<EngineError>(), Some(EngineError::Store { .. })));
+        assert!(engine.get_node("missing").is_err());
+        assert!(engine.health_score().is_err());
+
+        let error = engine.query("find Repo").await.unwrap_err();
+        assert!(error.to_string().contains("connection refused"), "{}", error);
+    }
+
+    #[test]
+    fn test_repository_requires_existing_path() {
+        let repository = CodeRepository::new(PathBuf::from("./does_not_exist_repository"));
+        assert!(repository.is_err());
+    }
 }
@@ -1,9 +1,16 @@
 #[cfg(test)]
 mod tests {
     use neuro_node_path_engine::{
-        core::{Neuron, Node, Cluster, NeuralChannel, Interface},
+        core::{interface::InterfaceType, node::NodeType, Neuron, Node, Cluster, NeuralChannel, Interface},
+        engine::{ClusterNavigator, NameIndex, PathResolver, QueryParser, QueryProcessor},
+        i18n::{StopWords, Translator},
+        EngineConfig, InMemoryGraphStore, GraphStore, QueryContext,
+        repository::{extract_debt_markers, extract_documentation, CodeAnalyzer},
         utils::Hasher,
+        validation::{DualPathConfig, DualPathValidator},
     };
+    use std::collections::HashMap;
+    use tokio_util::sync::CancellationToken;
 
     #[test]
     fn test_neuron_creation() {
@@ -69,6 +76,17 @@ mod tests {
         assert!(is_valid);
     }
 
+    #[test]
+    fn test_normalize_name() {
+        use neuro_node_path_engine::utils::normalize_name;
+
+        for name in ["getUserName", "get_user_name", "GetUserName", "get-user.name"] {
+            assert_eq!(normalize_name(name), "get user name");
+        }
+        assert_eq!(normalize_name("HTTPServer2Config"), "http server2 config");
+        assert_eq!(normalize_name("__"), "");
+    }
+
     #[test]
     fn test_interface_creation() {
         let interface = Interface::new(
@@ -77,4 +95,376 @@ mod tests {
         );
         assert_eq!(interface.name, "query_interface");
     }
+
+    #[test]
+    fn test_interface_compatibility() {
+        use neuro_node_path_engine::core::interface::{Compatibility, Incompatibility, MethodSignature};
+
+        let method = |name: &str, return_type: &str| MethodSignature {
+            name: name.to_string(),
+            parameters: vec![("term".to_string(), "&str".to_string())],
+            return_type: return_type.to_string(),
+            description: None,
+        };
+        let mut v1 = Interface::new("search".to_string(), InterfaceType::QueryInterface)
+            .with_schemas(
+                serde_json::json!({}),
+                serde_json::json!({ "properties": { "ids": {}, "score": {} } }),
+            );
+        v1.add_method(method("find", "Vec<String>"));
+        v1.add_method(method("count", "usize"));
+        assert_eq!(v1.is_compatible_with(&v1), Compatibility::Identical);
+
+        let mut v2 = v1.clone();
+        v2.add_method(method("suggest", "Vec<String>"));
+        assert_eq!(
+            v2.is_compatible_with(&v1),
+            Compatibility::Compatible { added_methods: vec!["suggest".to_string()] }
+        );
+
+        let mut v3 = v1.clone();
+        v3.exposed_methods = vec![method("find", "Vec<u64>")];
+        v3.input_schema = serde_json::json!({ "required": ["term"] });
+        v3.output_schema = serde_json::json!({ "properties": { "ids": {} } });
+        assert_eq!(
+            v3.is_compatible_with(&v1),
+            Compatibility::Breaking(vec![
+                Incompatibility::SignatureChanged {
+                    name: "find".to_string(),
+                    before: "find(term: &str) -> Vec<String>".to_string(),
+                    after: "find(term: &str) -> Vec<u64>".to_string(),
+                },
+                Incompatibility::MethodRemoved { name: "count".to_string() },
+                Incompatibility::InputPropertyRequired { property: "term".to_string() },
+                Incompatibility::OutputPropertyRemoved { property: "score".to_string() },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_builtin_regex_patterns_compile() {
+        assert!(QueryProcessor::try_new().is_ok());
+        assert!(CodeAnalyzer::try_new().is_ok());
+    }
+
+    #[test]
+    fn test_cross_validate_detailed() {
+        let validator = DualPathValidator::default();
+        let path1: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let path2: Vec<String> = ["c", "d", "b"].iter().map(|s| s.to_string()).collect();
+
+        let detail = validator.cross_validate_detailed(&path1, &path2);
+        assert_eq!(detail.shared, vec!["b".to_string(), "c".to_string()]);
+        assert_eq!(detail.jaccard, 0.5);
+        assert!(detail.intersect);
+        assert!(validator.cross_validate(&path1, &path2));
+
+        let disjoint = validator.cross_validate_detailed(&path1, &[]);
+        assert!(!disjoint.intersect);
+        assert_eq!(disjoint.jaccard, 0.0);
+    }
+
+    #[test]
+    fn test_dual_path_config_decision_matrix() {
+        let ids = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let validator = DualPathValidator::new(DualPathConfig {
+            min_node_len: 2,
+            min_cluster_len: 1,
+            require_overlap: true,
+        });
+
+        let verdict = validator.validate(&[], &[]).unwrap();
+        assert!(verdict.starts_with("INVALID"));
+
+        let verdict = validator.validate(&ids(&["a", "b"]), &ids(&["c"])).unwrap();
+        assert!(verdict.starts_with("INVALID"));
+
+        let verdict = validator
+            .validate_with_members(&ids(&["a"]), &ids(&["c"]), &ids(&["a"]))
+            .unwrap();
+        assert!(verdict.starts_with("WARNING"));
+
+        let verdict = validator
+            .validate_with_members(&ids(&["a", "b"]), &ids(&["c"]), &ids(&["a"]))
+            .unwrap();
+        assert!(verdict.starts_with("VALID"));
+    }
+
+    #[test]
+    fn test_doc_comment_extraction_per_language() {
+        let python = "def run():\n    \"\"\"Runs the job.\n\n    Retries once.\n    \"\"\"\n";
+        assert_eq!(
+            extract_documentation("job.py", python).as_deref(),
+            Some("Runs the job.\n\nRetries once.")
+        );
+        let kotlin = "/**\n * Loads users.\n */\nfun load() {}\n/**/\n";
+        assert_eq!(extract_documentation("Users.kt", kotlin).as_deref(), Some("Loads users."));
+        assert_eq!(extract_documentation("lib.rs", "//// not docs\nfn f() {}"), None);
+        assert_eq!(extract_documentation("notes.txt", "/// text"), None);
+    }
+
+    #[test]
+    fn test_code_analyzer_public_api() {
+        let code = r#"
+pub fn find_path(from: &str, limit: HashMap<String, usize>) -> Vec<String> { vec![] }
+fn private_helper() {}
+impl Engine {
+    pub async fn run(&self) {}
+}
+pub trait AuditSink {
+    fn record(&mut self, entry: Entry) -> anyhow::Result<()>;
+    fn flush(&self) { }
+}
+"#;
+        let analyzer = CodeAnalyzer::new();
+        let functions = analyzer.public_functions(code);
+        let names: Vec<_> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["find_path", "run"]);
+        assert_eq!(
+            functions[0].parameters,
+            vec![
+                ("from".to_string(), "&str".to_string()),
+                ("limit".to_string(), "HashMap<String, usize>".to_string()),
+            ]
+        );
+        assert_eq!(functions[0].return_type, "Vec<String>");
+        assert!(functions[1].parameters.is_empty());
+
+        let traits = analyzer.public_traits(code);
+        assert_eq!(traits.len(), 1);
+        assert_eq!(traits[0].0, "AuditSink");
+        assert_eq!(traits[0].1.len(), 2);
+        assert_eq!(traits[0].1[0].return_type, "anyhow::Result<()>");
+        assert_eq!(InterfaceType::from_name("AuditSink"), InterfaceType::AuditInterface);
+    }
+
+    #[test]
+    fn test_query_parser_fields_and_free_text() {
+        let query = QueryParser::parse("type:function lang:rust cluster:service name~user audit path").unwrap();
+        assert_eq!(query.type_filter, Some(NodeType::Function));
+        assert_eq!(query.lang_filter.as_deref(), Some("rust"));
+        assert_eq!(query.cluster_filter.as_deref(), Some("service"));
+        assert_eq!(query.name_match.as_deref(), Some("user"));
+        assert_eq!(query.free_text, "audit path");
+
+        let err = QueryParser::parse("kind:function").unwrap_err().to_string();
+        assert!(err.contains("kind:") && err.contains("type:"), "{}", err);
+        assert!(QueryParser::parse("lang:").is_err());
+    }
+
+    #[test]
+    fn test_indexed_resolution_matches_substring_semantics() {
+        let mut nodes: Vec<Node> = ["user", "user_service", "ser", "vice", "order", "", "user"]
+            .iter()
+            .map(|name| Node::new(name.to_string(), NodeType::Function, String::new()))
+            .collect();
+        nodes[4].parent_id = Some(nodes[1].id.clone());
+
+        let index = NameIndex::build(nodes.iter().map(|n| n.name.as_str())).unwrap();
+        let resolver = PathResolver::new();
+        for query in ["find user_service now", "orders", "nothing", "", "useruser"] {
+            let context = QueryContext::new(query);
+            let expected = resolver.resolve(&context, &nodes).unwrap();
+            let cancel = CancellationToken::new();
+            let indexed = resolver.resolve_indexed(&context, &nodes, &index, &cancel).unwrap();
+            assert_eq!(indexed, expected, "query {:?}", query);
+        }
+    }
+
+    #[test]
+    fn test_store_resolution_matches_sorted_slice_resolution() {
+        let mut nodes: Vec<Node> = ["user", "user_service", "user_repo", "order", "config"]
+            .iter()
+            .map(|name| Node::new(name.to_string(), NodeType::Function, String::new()))
+            .collect();
+        nodes[3].parent_id = Some(nodes[1].id.clone());
+        nodes[4].parent_id = Some(nodes[1].id.clone());
+        nodes[4].node_type = NodeType::Config;
+
+        let store = InMemoryGraphStore::new();
+        for node in &nodes {
+            store.nodes().insert(node.id.clone(), node.clone()).unwrap();
+        }
+        let keep = |node: &Node| node.node_type != NodeType::Config;
+        let mut kept: Vec<Node> = nodes.iter().filter(|n| keep(n)).cloned().collect();
+        kept.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let resolver = PathResolver::new();
+        let cancel = CancellationToken::new();
+        for query in ["find user_service now", "user", "order", "nothing"] {
+            let context = QueryContext::new(query);
+            let expected = resolver.resolve_partial(&context, &kept, None, &cancel).unwrap();
+            let streamed = resolver.resolve_in(&context, store.nodes(), &keep, None, &cancel).unwrap();
+            assert_eq!(streamed, expected, "query {:?}", query);
+        }
+    }
+
+    #[test]
+    fn test_cluster_summary_prefers_central_sentence() {
+        let mut cluster = Cluster::new("routing".to_string(), neuro_node_path_engine::core::cluster::ClusterType::Functional);
+        let mut nodes = HashMap::new();
+        for doc in [
+            "Resolves the node path for a query.",
+            "Caches the resolved node path.",
+            "Logs startup time.",
+        ] {
+            let mut node = Node::new("n".to_string(), NodeType::Function, "src/lib.rs".to_string());
+            node.metadata.documentation = Some(doc.to_string());
+            cluster.node_ids.insert(node.id.clone());
+            nodes.insert(node.id.clone(), node);
+        }
+
+        let navigator = ClusterNavigator::new();
+        let summary = navigator.summarize(&cluster, &nodes);
+        assert!(summary.contains("node path"), "{summary}");
+        let german = navigator.summarize_in(&cluster, &nodes, &Translator::new(), "de");
+        assert!(german.contains("Knoten Pfad"), "{german}");
+
+        for node in nodes.values_mut() {
+            node.metadata.documentation = None;
+        }
+        assert_eq!(navigator.summarize(&cluster, &nodes), "routing with 3 nodes");
+        assert_eq!(
+            navigator.summarize_in(&cluster, &nodes, &Translator::new(), "de"),
+            "routing mit 3 Knoten"
+        );
+    }
+
+    #[test]
+    fn test_translate_chain_falls_back_in_order() {
+        let translator = Translator::new();
+        let chain = |langs: &[&str]| langs.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+
+        let direct = translator.translate_chain("en", "node", &chain(&["de", "en"]));
+        assert_eq!(direct.text, "Knoten");
+        assert_eq!(direct.language.as_deref(), Some("de"));
+        assert!(!direct.fallback);
+
+        let english = translator.translate_chain("en", "node", &chain(&["es", "en"]));
+        assert_eq!(english.text, "node");
+        assert_eq!(english.language.as_deref(), Some("en"));
+        assert!(english.fallback);
+
+        let raw = translator.translate_chain("en", "synapse", &chain(&["es", "de"]));
+        assert_eq!(raw.text, "synapse");
+        assert_eq!(raw.language, None);
+        assert!(raw.fallback);
+    }
+
+    #[test]
+    fn test_entity_histogram_counts_every_kind() {
+        let code = "pub async fn load() {}\nfn parse() {}\nfn render() {}\nstruct Model;\nenum Kind {}\n";
+        let histogram = CodeAnalyzer::new().entity_histogram(code);
+
+        assert_eq!(histogram["async_function"], 1);
+        assert_eq!(histogram["function"], 2);
+        assert_eq!(histogram["data_structure"], 2);
+        assert_eq!(histogram["module"], 0);
+        assert_eq!(histogram.len(), 4);
+    }
+
+    #[test]
+    fn test_extract_entities_drops_stop_words() {
+        let processor = QueryProcessor::new();
+        assert_eq!(processor.extract_entities("find the user service"), vec!["user", "service"]);
+        assert_eq!(
+            processor.extract_entities_in("zeige alle Dienste über Knoten", "de"),
+            vec!["dienste", "knoten"]
+        );
+
+        let mut stop_words = StopWords::none();
+        stop_words.add("en", "Service");
+        let custom = QueryProcessor::new().with_stop_words(stop_words);
+        assert_eq!(custom.extract_entities("find the user service"), vec!["find", "user"]);
+    }
+
+    #[test]
+    fn test_engine_config_builder() {
+        let config = EngineConfig::builder()
+            .max_depth(16)
+            .enable_audit(false)
+            .supported_languages(["de"])
+            .seed(7)
+            .build()
+            .unwrap();
+        assert_eq!(config.max_depth, 16);
+        assert!(!config.enable_audit);
+        assert_eq!(config.supported_languages, vec!["de".to_string()]);
+        assert_eq!(config.seed, Some(7));
+        assert_eq!(config.audit_retention_days, EngineConfig::default().audit_retention_days);
+
+        assert!(EngineConfig::builder().max_depth(0).build().is_err());
+        assert!(EngineConfig::builder().supported_languages(Vec::<String>::new()).build().is_err());
+        assert!(EngineConfig::builder()
+            .query_timeout(std::time::Duration::ZERO)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_extract_debt_markers_from_comments() {
+        let code = "fn run() {\n    // TODO: handle retries\n    let todo_list = \"TODO\";\n    /* FIXME(ana): leaks */\n}\n# XXX-remove after migration\n";
+        let markers: Vec<String> = ["TODO", "FIXME", "XXX"].iter().map(|m| m.to_string()).collect();
+        let found = extract_debt_markers(code, &markers);
+
+        let summary: Vec<(&str, usize, &str)> = found
+            .iter()
+            .map(|m| (m.marker.as_str(), m.line, m.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("TODO", 2, "handle retries"),
+                ("FIXME", 4, "leaks"),
+                ("XXX", 6, "remove after migration"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_generates_client_stubs() {
+        use neuro_node_path_engine::core::{interface::MethodSignature, StubLanguage};
+
+        let mut interface = Interface::new("search_api".to_string(), InterfaceType::QueryInterface)
+            .with_schemas(
+                serde_json::json!({
+                    "properties": { "term": { "type": "string" }, "limit": { "type": "integer" } },
+                    "required": ["term"]
+                }),
+                serde_json::json!({}),
+            );
+        interface.add_method(MethodSignature {
+            name: "find_nodes".to_string(),
+            parameters: vec![
+                ("term".to_string(), "&str".to_string()),
+                ("limit".to_string(), "Option<usize>".to_string()),
+            ],
+            return_type: "anyhow::Result<Vec<String>>".to_string(),
+            description: Some("Node ids matching `term`.".to_string()),
+        });
+        interface.add_method(MethodSignature {
+            name: "stats".to_string(),
+            parameters: Vec::new(),
+            return_type: "HashMap<String, f64>".to_string(),
+            description: None,
+        });
+
+        let rust = interface.generate_stub(StubLanguage::Rust);
+        assert!(rust.contains("pub struct SearchApiInput {"));
+        assert!(rust.contains("    pub limit: Option<i64>,\n    pub term: String,"));
+        assert!(rust.contains(
+            "    pub fn find_nodes(&self, term: String, limit: Option<usize>) -> anyhow::Result<Vec<String>> {"
+        ));
+        assert!(rust.contains("serde_json::json!({ \"term\": term, \"limit\": limit })"));
+        assert!(rust.contains("pub fn stats(&self) -> anyhow::Result<HashMap<String, f64>>"));
+
+        let typescript = interface.generate_stub("ts".parse().unwrap());
+        assert!(typescript.contains("export interface SearchApiInput {\n  limit?: number;\n  term: string;\n}"));
+        assert!(typescript.contains(
+            "  async findNodes(term: string, limit: number | null): Promise<string[]> {"
+        ));
+        assert!(typescript.contains("this.transport(\"find_nodes\", { \"term\": term, \"limit\": limit })"));
+        assert!(typescript.contains("  async stats(): Promise<Record<string, number>> {"));
+        assert!(!interface.generate_stub(StubLanguage::Rust).contains("SearchApiOutput"));
+    }
 }
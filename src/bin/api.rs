@@ -2,18 +2,101 @@
 //! Exposes repository scanning, analysis, and code synthesis endpoints
 
 use axum::{
-    extract::{Json, State},
-    response::Json as JsonResponse,
-    routing::post,
-    Router,
+    body::Bytes,
+    error_handling::HandleErrorLayer,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Json, Path, Query, State,
+    },
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Json as JsonResponse, Response},
+    routing::{get, post},
+    BoxError, Router,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path as FsPath, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use neuro_node_path_engine::{
+    engine::{GraphFormat, PathStep, QueryResult},
+    repository::{CodeAnalyzer, CodeRepository},
+    EngineConfig, NeuroNodePathEngine,
+};
+use tokio::sync::{RwLock, Semaphore};
+use tower::{timeout::TimeoutLayer, ServiceBuilder};
+use tower_http::cors::{Any, CorsLayer};
 use cadsp_core::*;
 
-#[derive(Clone)]
+mod auth;
+mod error;
+mod idempotency;
+mod metrics;
+mod pagination;
+mod streaming;
+mod webhooks;
+
+use error::ApiError;
+use idempotency::{Claim, IdempotencyStore};
+use metrics::Metrics;
+use pagination::{PageParams, Paginated};
+
+/// Seconds clients are asked to wait before retrying a rejected scan.
+const SCAN_RETRY_AFTER_SECS: u64 = 5;
+
+/// Size of the chunks a graph export is streamed in.
+const GRAPH_EXPORT_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Settings read from the environment at startup.
+#[derive(Debug, Clone)]
+struct ApiConfig {
+    request_timeout: Duration,
+    max_concurrent_scans: usize,
+    batch_concurrency: usize,
+    /// Batches with more items than this are rejected.
+    max_batch_items: usize,
+    /// How long a scan's `Idempotency-Key` keeps returning the same result.
+    idempotency_ttl: Duration,
+    /// Bearer token for protected endpoints; they are disabled when unset.
+    api_token: Option<String>,
+    /// Graphs with more nodes than this are refused by the graph export.
+    graph_export_max_nodes: usize,
+    /// Directory of local checkouts laid out as `<owner>/<repo>`. Pushes to
+    /// a repository checked out here are pulled and re-indexed.
+    checkout_root: Option<PathBuf>,
+}
+
+impl ApiConfig {
+    fn from_env() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(env_or("REQUEST_TIMEOUT_SECS", 30)),
+            max_concurrent_scans: env_or("MAX_CONCURRENT_SCANS", 4) as usize,
+            batch_concurrency: env_or("BATCH_CONCURRENCY", 8) as usize,
+            max_batch_items: env_or("MAX_BATCH_ITEMS", 256) as usize,
+            idempotency_ttl: Duration::from_secs(env_or("IDEMPOTENCY_TTL_SECS", 24 * 60 * 60)),
+            api_token: std::env::var("API_TOKEN").ok().filter(|s| !s.is_empty()),
+            graph_export_max_nodes: env_or("GRAPH_EXPORT_MAX_NODES", 100_000) as usize,
+            checkout_root: std::env::var("CHECKOUT_ROOT")
+                .ok()
+                .filter(|s| !s.is_empty())
+                .map(PathBuf::from),
+        }
+    }
+}
+
+/// State shared by every handler. The scanner is shared so all requests
+/// draw on the same GitHub rate limit, and the engine keeps its graph
+/// between requests.
 struct AppState {
+    config: ApiConfig,
     github_token: String,
+    webhook_secret: Option<String>,
+    scanner: RepositoryScanner,
+    engine: Arc<RwLock<NeuroNodePathEngine>>,
+    metrics: Metrics,
+    scan_permits: Semaphore,
+    batch_permits: Arc<Semaphore>,
+    scan_keys: IdempotencyStore<ScanResponse>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,13 +106,39 @@ struct ScanRequest {
     sandbox_mode: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ScanResponse {
     scan_id: String,
     status: String,
     metadata: serde_json::Value,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct QueryRequest {
+    query: String,
+    /// Language to answer in; defaults to English.
+    #[serde(default)]
+    lang: Option<String>,
+}
+
+/// A query result with the justification of each hop on its node path.
+#[derive(Debug, Serialize)]
+struct ExplainResponse {
+    result: QueryResult,
+    steps: Vec<PathStep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphExportParams {
+    /// `dot`, `graphml` or `json` (the default).
+    format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NeighborhoodParams {
+    radius: Option<usize>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct AnalysisRequest {
     repo_id: String,
@@ -40,11 +149,48 @@ struct AnalysisRequest {
 struct AnalysisResponse {
     discoveries: Vec<DiscoveredObject>,
     neuro_path: NeuroNodePath,
+    /// Functions, async functions, data structures and modules in the code.
+    entity_histogram: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchAnalysisItem {
+    id: String,
+    code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchAnalysisRequest {
+    items: Vec<BatchAnalysisItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchItemResult {
+    id: String,
+    status: String,
+    discoveries: Vec<DiscoveredObject>,
+    entity_histogram: BTreeMap<String, usize>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BatchSummary {
+    total_items: usize,
+    succeeded: usize,
+    failed: usize,
+    total_discoveries: usize,
+    discoveries_by_family: HashMap<String, usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchAnalysisResponse {
+    results: Vec<BatchItemResult>,
+    summary: BatchSummary,
 }
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt::init();
+    init_tracing();
 
     let github_token = std::env::var("GITHUB_TOKEN")
         .unwrap_or_else(|_| {
@@ -52,12 +198,59 @@ async fn main() {
             String::new()
         });
 
-    let state = AppState { github_token };
+    let config = ApiConfig::from_env();
 
-    let app = Router::new()
+    let metrics = Metrics::new().expect("Failed to register metrics");
+    let webhook_secret = std::env::var("GITHUB_WEBHOOK_SECRET")
+        .ok()
+        .filter(|s| !s.is_empty());
+    if webhook_secret.is_none() {
+        tracing::warn!("GITHUB_WEBHOOK_SECRET not set, GitHub webhooks disabled");
+    }
+    if config.api_token.is_none() {
+        tracing::warn!("API_TOKEN not set, graph export disabled");
+    }
+
+    let engine = NeuroNodePathEngine::new(EngineConfig::default())
+        .expect("Failed to initialize search engine");
+    engine.warm().await.expect("Failed to warm search engine");
+    let request_timeout = config.request_timeout;
+
+    let state = AppState {
+        scanner: RepositoryScanner::new(github_token.clone()),
+        engine: Arc::new(RwLock::new(engine)),
+        github_token,
+        webhook_secret,
+        metrics,
+        scan_permits: Semaphore::new(config.max_concurrent_scans),
+        batch_permits: Arc::new(Semaphore::new(config.batch_concurrency)),
+        scan_keys: IdempotencyStore::new(config.idempotency_ttl),
+        config,
+    };
+
+    let api_routes = Router::new()
         .route("/api/v1/repositories/scan", post(scan_repository))
+        .route("/api/v1/query", post(query_engine))
+        .route("/api/v1/query/explain", post(explain_query))
+        .route("/api/v1/nodes/:id", get(get_node_neighborhood))
+        .route("/api/v1/graph", get(export_graph))
+        .route("/api/v1/audit", get(list_audit_entries))
+        .route("/api/v1/audit/stream", get(audit_stream))
         .route("/api/v1/analysis/discover", post(analyze_code))
+        .route("/api/v1/analysis/batch", post(analyze_batch))
+        .route("/api/v1/ws", get(ws_session))
+        .route("/api/v1/webhooks/github", post(github_webhook))
+        .layer(cors_layer());
+
+    let app = Router::new()
+        .merge(api_routes)
         .route("/health", axum::routing::get(health_check))
+        .route("/metrics", get(prometheus_metrics))
+        .layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(handle_middleware_error))
+                .layer(TimeoutLayer::new(request_timeout)),
+        )
         .with_state(Arc::new(state));
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080")
@@ -71,26 +264,175 @@ async fn main() {
         .expect("Server error");
 }
 
+/// Logs as JSON when `LOG_FORMAT=json`, otherwise as human-readable text.
+fn init_tracing() {
+    let builder = tracing_subscriber::fmt();
+    match std::env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => builder.json().init(),
+        _ => builder.init(),
+    }
+}
+
+/// Reads a numeric setting from the environment, falling back to `default`
+/// when unset or unparsable.
+fn env_or(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Builds the CORS policy for `/api/v1/*` from `ALLOWED_ORIGINS`, a
+/// comma-separated origin list. `*` allows any origin (development only);
+/// unset or empty allows none.
+fn cors_layer() -> CorsLayer {
+    let layer = CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            header::HeaderName::from_static("idempotency-key"),
+        ])
+        .expose_headers([header::RETRY_AFTER]);
+
+    let allowed = std::env::var("ALLOWED_ORIGINS").unwrap_or_default();
+    if allowed.trim() == "*" {
+        tracing::warn!("CORS allows any origin; do not use ALLOWED_ORIGINS=* in production");
+        return layer.allow_origin(Any);
+    }
+
+    let origins: Vec<HeaderValue> = allowed
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .filter_map(|origin| match origin.parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                tracing::warn!("Ignoring invalid CORS origin: {}", origin);
+                None
+            }
+        })
+        .collect();
+
+    layer.allow_origin(origins)
+}
+
+async fn handle_middleware_error(err: BoxError) -> ApiError {
+    if err.is::<tower::timeout::error::Elapsed>() {
+        ApiError::new(
+            StatusCode::GATEWAY_TIMEOUT,
+            "REQUEST_TIMEOUT",
+            "Request exceeded the configured timeout",
+        )
+    } else {
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            format!("Unhandled middleware error: {}", err),
+        )
+    }
+}
+
+/// Scans a repository. A retried request carrying the same
+/// `Idempotency-Key` as a completed scan gets that scan's response instead
+/// of starting another; failed scans are not remembered. Reusing a key
+/// with a different body is rejected with 422.
+#[tracing::instrument(
+    skip_all,
+    fields(request_id = %uuid::Uuid::new_v4(), repo_url = %req.repo_url)
+)]
 async fn scan_repository(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(req): Json<ScanRequest>,
-) -> JsonResponse<ScanResponse> {
-    tracing::info!("Scan request for: {}", req.repo_url);
+) -> Result<JsonResponse<ScanResponse>, ApiError> {
+    tracing::info!("Scan requested");
+
+    let Some(key) = headers
+        .get("Idempotency-Key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+    else {
+        return run_scan(&state, &req).await.map(JsonResponse);
+    };
+
+    let body_hash = scan_body_hash(&req);
+    match state.scan_keys.claim(&key, &body_hash) {
+        Claim::Mismatch => Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "IDEMPOTENCY_KEY_REUSED",
+            "This Idempotency-Key was already used with a different request body",
+        )),
+        Claim::Completed(response) => {
+            tracing::info!(scan_id = %response.scan_id, "Returning scan for repeated idempotency key");
+            Ok(JsonResponse(response))
+        }
+        Claim::InProgress => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "SCAN_IN_PROGRESS",
+            "A scan with this Idempotency-Key is still running",
+        )
+        .with_retry_after(SCAN_RETRY_AFTER_SECS)),
+        Claim::New => {
+            let result = run_scan(&state, &req).await;
+            match &result {
+                Ok(response) if response.status == "completed" => {
+                    state.scan_keys.complete(&key, response.clone())
+                }
+                _ => state.scan_keys.release(&key),
+            }
+            result.map(JsonResponse)
+        }
+    }
+}
 
-    let scanner = RepositoryScanner::new(state.github_token.clone());
+/// SHA-256 of the scan request as JSON, identifying the body an
+/// `Idempotency-Key` was first used with.
+fn scan_body_hash(req: &ScanRequest) -> String {
+    use sha2::{Digest, Sha256};
+    let body = serde_json::to_vec(req).unwrap_or_default();
+    hex::encode(Sha256::digest(&body))
+}
 
-    match scanner.scan(&req.repo_url).await {
+async fn run_scan(state: &Arc<AppState>, req: &ScanRequest) -> Result<ScanResponse, ApiError> {
+    let _permit = state.scan_permits.try_acquire().map_err(|_| {
+        tracing::warn!("Scan rejected, concurrency limit reached");
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "SCAN_CAPACITY_EXCEEDED",
+            format!(
+                "Too many scans in progress (limit {}), retry later",
+                state.config.max_concurrent_scans
+            ),
+        )
+        .with_retry_after(SCAN_RETRY_AFTER_SECS)
+    })?;
+    state.metrics.scans_total.inc();
+
+    let started = Instant::now();
+    let result = state.scanner.scan(&req.repo_url).await;
+    state
+        .metrics
+        .scan_duration_seconds
+        .observe(started.elapsed().as_secs_f64());
+    let refresh_state = state.clone();
+    tokio::spawn(async move {
+        metrics::refresh_rate_limit(&refresh_state.metrics, &refresh_state.github_token).await;
+    });
+
+    match result {
         Ok(metadata) => {
-            tracing::info!("Scan completed: {}", metadata.scan_id);
-            JsonResponse(ScanResponse {
+            tracing::info!(scan_id = %metadata.scan_id, "Scan completed");
+            Ok(ScanResponse {
                 scan_id: metadata.scan_id.clone(),
                 status: "completed".to_string(),
                 metadata: serde_json::to_value(&metadata).unwrap_or(serde_json::json!({})),
             })
         }
         Err(e) => {
-            tracing::error!("Scan failed: {}", e);
-            JsonResponse(ScanResponse {
+            tracing::error!(error = %e, "Scan failed");
+            state.metrics.record_error(&e);
+            Ok(ScanResponse {
                 scan_id: "ERROR".to_string(),
                 status: format!("failed: {}", e),
                 metadata: serde_json::json!({}),
@@ -99,11 +441,158 @@ async fn scan_repository(
     }
 }
 
+/// Runs a path query against the shared engine.
+#[tracing::instrument(skip_all, fields(request_id = %uuid::Uuid::new_v4()))]
+async fn query_engine(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<QueryRequest>,
+) -> Result<JsonResponse<QueryResult>, ApiError> {
+    tracing::info!(query = %req.query, "Query requested");
+    state.metrics.queries_total.inc();
+    let _timer = state.metrics.query_duration_seconds.start_timer();
+
+    let engine = state.engine.read().await;
+    let language = req.lang.as_deref().unwrap_or("en");
+    let result = engine.query_in(&req.query, language).await.map_err(|e| {
+        tracing::error!(error = %e, "Query failed");
+        ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "QUERY_FAILED", e.to_string())
+    })?;
+    tracing::info!(nodes = result.node_path.len(), "Query completed");
+    Ok(JsonResponse(result))
+}
+
+/// Runs a path query like `/api/v1/query` and explains why each node is on
+/// the resulting path.
+#[tracing::instrument(skip_all, fields(request_id = %uuid::Uuid::new_v4()))]
+async fn explain_query(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<QueryRequest>,
+) -> Result<JsonResponse<ExplainResponse>, ApiError> {
+    tracing::info!(query = %req.query, "Query explanation requested");
+    state.metrics.queries_total.inc();
+    let _timer = state.metrics.query_duration_seconds.start_timer();
+
+    let engine = state.engine.read().await;
+    let explained = run_explained_query(&engine, &req).await.map_err(|e| {
+        tracing::error!(error = %e, "Query failed");
+        ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "QUERY_FAILED", e.to_string())
+    })?;
+    Ok(JsonResponse(explained))
+}
+
+/// Answers `req` in its language, defaulting to English, and explains each
+/// hop of the resulting path against the same engine.
+async fn run_explained_query(
+    engine: &NeuroNodePathEngine,
+    req: &QueryRequest,
+) -> anyhow::Result<ExplainResponse> {
+    let language = req.lang.as_deref().unwrap_or("en");
+    let result = engine.query_in(&req.query, language).await?;
+    let steps = engine.explain_path(&result)?;
+    Ok(ExplainResponse { result, steps })
+}
+
+/// Streams every node and channel as DOT, GraphML or JSON, chosen with
+/// `?format=`. Requires `Authorization: Bearer $API_TOKEN` and refuses
+/// graphs over `GRAPH_EXPORT_MAX_NODES` nodes. The graph is copied under a
+/// short read lock and streamed from the copy, so slow clients never hold
+/// the engine.
+#[tracing::instrument(skip_all, fields(request_id = %uuid::Uuid::new_v4()))]
+async fn export_graph(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<GraphExportParams>,
+) -> Result<Response, ApiError> {
+    let Some(token) = state.config.api_token.as_deref() else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "GRAPH_EXPORT_DISABLED",
+            "Graph export is disabled because API_TOKEN is not configured",
+        ));
+    };
+    if !auth::bearer_token_matches(&headers, token) {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHORIZED",
+            "A valid bearer token is required",
+        ));
+    }
+    let format: GraphFormat = params
+        .format
+        .as_deref()
+        .unwrap_or("json")
+        .parse()
+        .map_err(|e: anyhow::Error| {
+            ApiError::new(StatusCode::BAD_REQUEST, "INVALID_FORMAT", e.to_string())
+        })?;
+
+    let snapshot = state.engine.read().await.snapshot().map_err(|e| {
+        ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "GRAPH_STORE_FAILED", e.to_string())
+    })?;
+    let node_count = snapshot.nodes.len();
+    if node_count > state.config.graph_export_max_nodes {
+        return Err(ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "GRAPH_TOO_LARGE",
+            format!(
+                "Graph has {} nodes, more than the export limit of {}",
+                node_count, state.config.graph_export_max_nodes
+            ),
+        ));
+    }
+
+    tracing::info!(nodes = node_count, ?format, "Graph export requested");
+    let body = streaming::stream_body(GRAPH_EXPORT_CHUNK_BYTES, move |out| {
+        snapshot.write_graph(format, out)
+    });
+    Ok(([(header::CONTENT_TYPE, format.content_type())], body).into_response())
+}
+
+/// Returns a node with the nodes and channels within `radius` hops
+/// (default 1).
+#[tracing::instrument(skip_all, fields(request_id = %uuid::Uuid::new_v4(), node_id = %id))]
+async fn get_node_neighborhood(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(params): Query<NeighborhoodParams>,
+) -> Result<JsonResponse<neuro_node_path_engine::engine::Neighborhood>, ApiError> {
+    let engine = state.engine.read().await;
+    engine
+        .neighborhood(&id, params.radius.unwrap_or(1))
+        .map_err(|e| {
+            ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "GRAPH_STORE_FAILED", e.to_string())
+        })?
+        .map(JsonResponse)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NODE_NOT_FOUND",
+                format!("No node with id {}", id),
+            )
+        })
+}
+
+/// Pages through the engine's audit trail, oldest entry first.
+async fn list_audit_entries(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<PageParams>,
+) -> JsonResponse<Paginated<neuro_node_path_engine::engine::audit_trail::AuditEntry>> {
+    let entries = state.engine.read().await.audit_entries();
+    JsonResponse(Paginated::from_items(entries, &params))
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(request_id = %uuid::Uuid::new_v4(), repo_id = %req.repo_id)
+)]
 async fn analyze_code(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(req): Json<AnalysisRequest>,
 ) -> JsonResponse<AnalysisResponse> {
-    tracing::info!("Analysis request for repo: {}", req.repo_id);
+    tracing::info!("Analysis requested");
+    state.metrics.analyses_total.inc();
+    let _timer = state.metrics.analysis_duration_seconds.start_timer();
+    let entity_histogram = CodeAnalyzer::new().entity_histogram(&req.code_content);
 
     match biophysical_patterns::PatternDetector::detect(&req.code_content) {
         Ok(discoveries) => {
@@ -114,31 +603,595 @@ async fn analyze_code(
 
             match neuro_node_path::NeuroNodePathEngine::compute_path(&req.repo_id, &objects) {
                 Ok(neuro_path) => {
-                    tracing::info!("Analysis completed: {} discoveries", discoveries.len());
+                    tracing::info!(discoveries = discoveries.len(), "Analysis completed");
                     JsonResponse(AnalysisResponse {
                         discoveries,
                         neuro_path,
+                        entity_histogram,
                     })
                 }
                 Err(e) => {
-                    tracing::error!("Path computation failed: {}", e);
+                    tracing::error!(error = %e, "Path computation failed");
+                    state.metrics.record_error(&e);
                     JsonResponse(AnalysisResponse {
                         discoveries: vec![],
                         neuro_path: Default::default(),
+                        entity_histogram,
                     })
                 }
             }
         }
         Err(e) => {
-            tracing::error!("Pattern detection failed: {}", e);
+            tracing::error!(error = %e, "Pattern detection failed");
+            state.metrics.record_error(&e);
             JsonResponse(AnalysisResponse {
                 discoveries: vec![],
                 neuro_path: Default::default(),
+                entity_histogram,
             })
         }
     }
 }
 
+/// Runs pattern detection over many snippets on a bounded blocking pool.
+/// Failures are reported per item; results keep the request order. Batches
+/// over `MAX_BATCH_ITEMS` items are rejected with 413.
+#[tracing::instrument(skip_all, fields(request_id = %uuid::Uuid::new_v4()))]
+async fn analyze_batch(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BatchAnalysisRequest>,
+) -> Result<JsonResponse<BatchAnalysisResponse>, ApiError> {
+    tracing::info!(items = req.items.len(), "Batch analysis requested");
+    if req.items.len() > state.config.max_batch_items {
+        tracing::warn!("Batch rejected, too many items");
+        return Err(ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "BATCH_TOO_LARGE",
+            format!(
+                "Batch has {} items, more than the limit of {}",
+                req.items.len(),
+                state.config.max_batch_items
+            ),
+        ));
+    }
+    state.metrics.analyses_total.inc();
+    let _timer = state.metrics.analysis_duration_seconds.start_timer();
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, item) in req.items.into_iter().enumerate() {
+        let permits = state.batch_permits.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let BatchAnalysisItem { id, code } = item;
+            let detection = tokio::task::spawn_blocking(move || {
+                (
+                    biophysical_patterns::PatternDetector::detect(&code),
+                    CodeAnalyzer::new().entity_histogram(&code),
+                )
+            })
+            .await;
+
+            let result = match detection {
+                Ok((Ok(discoveries), entity_histogram)) => BatchItemResult {
+                    id,
+                    status: "completed".to_string(),
+                    discoveries,
+                    entity_histogram,
+                    error: None,
+                },
+                Ok((Err(e), entity_histogram)) => BatchItemResult {
+                    id,
+                    status: "failed".to_string(),
+                    discoveries: vec![],
+                    entity_histogram,
+                    error: Some(e.to_string()),
+                },
+                Err(e) => BatchItemResult {
+                    id,
+                    status: "failed".to_string(),
+                    discoveries: vec![],
+                    entity_histogram: BTreeMap::new(),
+                    error: Some(format!("detection task aborted: {}", e)),
+                },
+            };
+            (index, result)
+        });
+    }
+
+    let mut indexed = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(entry) => indexed.push(entry),
+            Err(e) => tracing::error!("Batch task failed to join: {}", e),
+        }
+    }
+    indexed.sort_by_key(|(index, _)| *index);
+    let results: Vec<BatchItemResult> = indexed.into_iter().map(|(_, r)| r).collect();
+
+    let mut summary = BatchSummary {
+        total_items: results.len(),
+        ..Default::default()
+    };
+    for result in &results {
+        if result.error.is_some() {
+            summary.failed += 1;
+        } else {
+            summary.succeeded += 1;
+        }
+        summary.total_discoveries += result.discoveries.len();
+        for discovery in &result.discoveries {
+            *summary
+                .discoveries_by_family
+                .entry(discovery.pattern_family.clone())
+                .or_insert(0) += 1;
+        }
+    }
+
+    tracing::info!(
+        succeeded = summary.succeeded,
+        failed = summary.failed,
+        "Batch analysis completed"
+    );
+    Ok(JsonResponse(BatchAnalysisResponse { results, summary }))
+}
+
+/// Verifies a GitHub push webhook and answers 202 straight away. In the
+/// background it pulls the pushed repository's checkout under
+/// `CHECKOUT_ROOT` and re-indexes its changed files in the engine, and
+/// rescans it. Requests with a missing or invalid signature get 401 and
+/// are not processed.
+async fn github_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, JsonResponse<serde_json::Value>), ApiError> {
+    let secret = state.webhook_secret.as_deref().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "WEBHOOK_NOT_CONFIGURED",
+            "GitHub webhooks are disabled on this server",
+        )
+    })?;
+
+    let signature = headers
+        .get("X-Hub-Signature-256")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !webhooks::verify_signature(secret, &body, signature) {
+        tracing::warn!("Rejected GitHub webhook with invalid signature");
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "INVALID_SIGNATURE",
+            "X-Hub-Signature-256 does not match the payload",
+        ));
+    }
+
+    let event_type = headers
+        .get("X-GitHub-Event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if event_type != "push" {
+        return Ok((
+            StatusCode::ACCEPTED,
+            JsonResponse(serde_json::json!({ "status": "ignored", "event": event_type })),
+        ));
+    }
+
+    let event: webhooks::PushEvent = serde_json::from_slice(&body).map_err(|e| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "INVALID_PAYLOAD",
+            format!("Malformed push payload: {}", e),
+        )
+    })?;
+    let changed_files = event.changed_files();
+    tracing::info!(
+        repository = %event.repository.full_name,
+        git_ref = %event.git_ref,
+        changed_files = changed_files.len(),
+        "Push received, scheduling rescan"
+    );
+
+    let reindex_scheduled = match state.config.checkout_root.as_deref() {
+        Some(root) => {
+            let full_name = event.repository.full_name.clone();
+            let checkout = root.join(&full_name);
+            let reindex_state = state.clone();
+            let files = changed_files.clone();
+            tokio::spawn(async move {
+                let reindexed = reindex_checkout(&reindex_state, checkout, &full_name, &files);
+                if let Err(e) = reindexed.await {
+                    tracing::error!(repo_id = %full_name, error = %e, "Webhook reindex failed");
+                }
+            });
+            true
+        }
+        None => false,
+    };
+
+    let repo_url = event.repository.html_url.clone();
+    let scan_state = state.clone();
+    tokio::spawn(async move {
+        let Ok(_permit) = scan_state.scan_permits.acquire().await else {
+            return;
+        };
+        scan_state.metrics.scans_total.inc();
+        match scan_state.scanner.scan(&repo_url).await {
+            Ok(metadata) => {
+                tracing::info!(scan_id = %metadata.scan_id, repo_url = %repo_url, "Webhook rescan completed")
+            }
+            Err(e) => {
+                tracing::error!(repo_url = %repo_url, error = %e, "Webhook rescan failed");
+                scan_state.metrics.record_error(&e);
+            }
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        JsonResponse(serde_json::json!({
+            "status": "accepted",
+            "repo_url": event.repository.html_url,
+            "changed_files": changed_files,
+            "reindex_scheduled": reindex_scheduled,
+        })),
+    ))
+}
+
+/// Pulls `checkout` and re-indexes `changed_files` in the `repo_id`
+/// namespace, or the whole checkout if the namespace is not indexed yet.
+/// Does nothing when the repository is not checked out. The engine is
+/// only locked for the reindex itself, not the pull.
+async fn reindex_checkout(
+    state: &AppState,
+    checkout: PathBuf,
+    repo_id: &str,
+    changed_files: &[String],
+) -> anyhow::Result<()> {
+    if !checkout.is_dir() {
+        tracing::info!(repo_id, "No local checkout, skipping reindex");
+        return Ok(());
+    }
+
+    let pulled = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(&checkout)
+        .args(["pull", "--ff-only", "--quiet"])
+        .status()
+        .await;
+    if !matches!(pulled, Ok(status) if status.success()) {
+        anyhow::bail!("Could not pull the local checkout of {}", repo_id);
+    }
+
+    let repository = CodeRepository::new(checkout)?;
+    let mut engine = state.engine.write().await;
+    let incremental = engine.repositories()?.iter().any(|r| r == repo_id);
+    let report = engine.reindex_files(repo_id, &repository, changed_files).await?;
+    tracing::info!(
+        repo_id,
+        files = report.files_indexed,
+        skipped = report.files_skipped,
+        unreadable = report.unreadable_files.len(),
+        incremental,
+        "Reindex completed"
+    );
+    Ok(())
+}
+
 async fn health_check() -> &'static str {
     "✓ CADSP API operational"
 }
+
+/// Prometheus scrape target. Intentionally unauthenticated so scrapers can
+/// reach it without the API token.
+async fn prometheus_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+/// Per-connection state for the interactive WebSocket session. Dropped when
+/// the socket closes. Its engine is separate from the shared one, so what a
+/// session indexes is visible only to that session's queries.
+struct WsSession {
+    scans: Vec<RepositoryScanMetadata>,
+    discoveries: Vec<DiscoveredObject>,
+    engine: NeuroNodePathEngine,
+}
+
+impl WsSession {
+    fn new() -> anyhow::Result<Self> {
+        Ok(Self {
+            scans: Vec::new(),
+            discoveries: Vec::new(),
+            engine: NeuroNodePathEngine::new(EngineConfig::default())?,
+        })
+    }
+}
+
+/// Pushes each new audit entry to the client as `{"event": "audit_entry"}`.
+/// Entries lost because the client fell behind are reported as
+/// `{"event": "dropped", "count": n}` before the next delivered entry.
+async fn audit_stream(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    ws.on_upgrade(move |socket| run_audit_stream(socket, state))
+}
+
+async fn run_audit_stream(mut socket: WebSocket, state: Arc<AppState>) {
+    let mut subscription = state.engine.read().await.subscribe_audit();
+    let mut reported_drops = 0;
+
+    loop {
+        let entry = tokio::select! {
+            entry = subscription.recv() => entry,
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+        let Some(entry) = entry else { break };
+
+        if subscription.dropped() > reported_drops {
+            let dropped = serde_json::json!({
+                "event": "dropped",
+                "count": subscription.dropped() - reported_drops,
+            });
+            reported_drops = subscription.dropped();
+            if socket.send(Message::Text(dropped.to_string())).await.is_err() {
+                break;
+            }
+        }
+        let event = serde_json::json!({ "event": "audit_entry", "entry": entry });
+        if socket.send(Message::Text(event.to_string())).await.is_err() {
+            break;
+        }
+    }
+
+    tracing::info!(dropped = subscription.dropped(), "Audit stream closed");
+}
+
+async fn ws_session(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+) -> Response {
+    ws.on_upgrade(move |socket| run_ws_session(socket, state))
+}
+
+async fn run_ws_session(mut socket: WebSocket, state: Arc<AppState>) {
+    tracing::info!("WebSocket session opened");
+
+    let mut session = match WsSession::new() {
+        Ok(session) => session,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to create session engine");
+            let event = serde_json::json!({ "event": "error", "message": e.to_string() });
+            let _ = socket.send(Message::Text(event.to_string())).await;
+            return;
+        }
+    };
+
+    while let Some(Ok(message)) = socket.recv().await {
+        let input = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+
+        let command = input.split_whitespace().next().unwrap_or_default().to_string();
+        let started = serde_json::json!({ "event": "started", "command": command });
+        if socket.send(Message::Text(started.to_string())).await.is_err() {
+            break;
+        }
+
+        let checkout_root = state.config.checkout_root.as_deref();
+        let outcome = run_ws_command(&state.scanner, checkout_root, &mut session, input).await;
+        if socket.send(Message::Text(outcome.to_string())).await.is_err() {
+            break;
+        }
+    }
+
+    tracing::info!(
+        "WebSocket session closed after {} scans, {} discoveries and {} indexed nodes",
+        session.scans.len(),
+        session.discoveries.len(),
+        session.engine.node_count().unwrap_or_default()
+    );
+}
+
+/// Executes one CLI-style command against the session and returns the JSON
+/// event describing its outcome. `index` only accepts paths inside
+/// `checkout_root` and is disabled without one.
+async fn run_ws_command(
+    scanner: &RepositoryScanner,
+    checkout_root: Option<&FsPath>,
+    session: &mut WsSession,
+    input: &str,
+) -> serde_json::Value {
+    match input {
+        "help" => serde_json::json!({
+            "event": "result",
+            "command": "help",
+            "commands": ["scan <url>", "analyze <code>", "index <path>", "query <text>", "help"],
+        }),
+        s if s.starts_with("index ") => {
+            let path = s.trim_start_matches("index ").trim();
+            let indexed = match checkout_path(checkout_root, path).and_then(CodeRepository::new) {
+                Ok(repository) => session.engine.index_repository(&repository).await,
+                Err(e) => Err(e),
+            };
+            match indexed {
+                Ok(report) => serde_json::json!({
+                    "event": "result",
+                    "command": "index",
+                    "report": report,
+                    "node_count": session.engine.node_count().ok(),
+                }),
+                Err(e) => serde_json::json!({
+                    "event": "error",
+                    "command": "index",
+                    "message": e.to_string(),
+                }),
+            }
+        }
+        s if s.starts_with("query ") => {
+            let text = s.trim_start_matches("query ").trim();
+            match session.engine.query(text).await {
+                Ok(result) => serde_json::json!({
+                    "event": "result",
+                    "command": "query",
+                    "result": result,
+                }),
+                Err(e) => serde_json::json!({
+                    "event": "error",
+                    "command": "query",
+                    "message": e.to_string(),
+                }),
+            }
+        }
+        s if s.starts_with("scan ") => {
+            let url = s.trim_start_matches("scan ").trim();
+            match scanner.scan(url).await {
+                Ok(metadata) => {
+                    let event = serde_json::json!({
+                        "event": "result",
+                        "command": "scan",
+                        "metadata": metadata,
+                    });
+                    session.scans.push(metadata);
+                    event
+                }
+                Err(e) => serde_json::json!({
+                    "event": "error",
+                    "command": "scan",
+                    "message": e.to_string(),
+                }),
+            }
+        }
+        s if s.starts_with("analyze ") => {
+            let code = s.trim_start_matches("analyze ");
+            match biophysical_patterns::PatternDetector::detect(code) {
+                Ok(discoveries) => {
+                    let event = serde_json::json!({
+                        "event": "result",
+                        "command": "analyze",
+                        "discoveries": discoveries,
+                    });
+                    session.discoveries.extend(discoveries);
+                    event
+                }
+                Err(e) => serde_json::json!({
+                    "event": "error",
+                    "command": "analyze",
+                    "message": e.to_string(),
+                }),
+            }
+        }
+        _ => serde_json::json!({
+            "event": "error",
+            "message": "Unknown command. Try 'help'.",
+        }),
+    }
+}
+
+/// Resolves `path` against `checkout_root`, refusing paths that leave it.
+fn checkout_path(checkout_root: Option<&FsPath>, path: &str) -> anyhow::Result<PathBuf> {
+    let Some(root) = checkout_root else {
+        anyhow::bail!("Indexing is disabled because CHECKOUT_ROOT is not configured");
+    };
+    let root = root.canonicalize()?;
+    let resolved = root.join(path).canonicalize()?;
+    if !resolved.starts_with(&root) {
+        anyhow::bail!("{} is outside CHECKOUT_ROOT", path);
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn run(root: &FsPath, session: &mut WsSession, input: &str) -> serde_json::Value {
+        let scanner = RepositoryScanner::new(String::new());
+        run_ws_command(&scanner, Some(root), session, input).await
+    }
+
+    #[tokio::test]
+    async fn test_ws_index_then_query_uses_the_session_engine() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir(root.path().join("repo")).unwrap();
+        std::fs::write(root.path().join("repo/router.rs"), "fn route() {}").unwrap();
+        let mut session = WsSession::new().unwrap();
+
+        let indexed = run(root.path(), &mut session, "index repo").await;
+        assert_eq!(indexed["event"], "result");
+        assert_eq!(indexed["report"]["files_indexed"], 1);
+        assert_eq!(session.engine.node_count().unwrap(), 1);
+
+        let queried = run(root.path(), &mut session, "query router").await;
+        assert_eq!(queried["event"], "result");
+        assert_eq!(queried["command"], "query");
+        assert!(queried["result"]["node_path"].as_array().is_some());
+        assert_eq!(WsSession::new().unwrap().engine.node_count().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_explained_query_justifies_each_step() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("router.rs"), "fn route() {}").unwrap();
+        let mut engine = NeuroNodePathEngine::new(EngineConfig::default()).unwrap();
+        let repository = CodeRepository::new(root.path().to_path_buf()).unwrap();
+        engine.index_repository(&repository).await.unwrap();
+        let router = root.path().join("router.rs").to_string_lossy().into_owned();
+
+        let req = QueryRequest {
+            query: format!("where is {}", router),
+            lang: Some("de".to_string()),
+        };
+        let explained = run_explained_query(&engine, &req).await.unwrap();
+        assert_eq!(explained.result.language, "de");
+        assert_eq!(explained.steps.len(), explained.result.node_path.len());
+        assert_eq!(explained.steps[0].node_name, router);
+
+        let body = serde_json::to_value(&explained).unwrap();
+        assert_eq!(body["steps"][0]["reason"]["MatchedQuery"]["term"], router.as_str());
+    }
+
+    #[test]
+    fn test_idempotency_key_reused_with_another_body_is_a_mismatch() {
+        let scan = |repo_url: &str| ScanRequest {
+            repo_url: repo_url.to_string(),
+            analysis_depth: None,
+            sandbox_mode: None,
+        };
+        let store: IdempotencyStore<ScanResponse> = IdempotencyStore::new(Duration::from_secs(60));
+        let first = scan_body_hash(&scan("https://github.com/a/b"));
+        assert!(matches!(store.claim("key", &first), Claim::New));
+        assert!(matches!(store.claim("key", &first), Claim::InProgress));
+
+        let other = scan_body_hash(&scan("https://github.com/a/c"));
+        assert_ne!(first, other);
+        assert!(matches!(store.claim("key", &other), Claim::Mismatch));
+    }
+
+    #[tokio::test]
+    async fn test_ws_index_rejects_paths_outside_checkout_root() {
+        let root = tempfile::tempdir().unwrap();
+        let mut session = WsSession::new().unwrap();
+
+        let escaped = run(root.path(), &mut session, "index ..").await;
+        assert_eq!(escaped["event"], "error");
+        assert_eq!(escaped["command"], "index");
+        assert_eq!(session.engine.node_count().unwrap(), 0);
+
+        let scanner = RepositoryScanner::new(String::new());
+        let disabled = run_ws_command(&scanner, None, &mut session, "index .").await;
+        assert_eq!(disabled["event"], "error");
+    }
+}
@@ -0,0 +1,21 @@
+//! Bearer token checks for protected endpoints
+
+use axum::http::{header, HeaderMap};
+
+/// True when `headers` carry `Authorization: Bearer <expected>`. The token
+/// comparison takes the same time wherever the first difference is.
+pub fn bearer_token_matches(headers: &HeaderMap, expected: &str) -> bool {
+    let Some(given) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
@@ -0,0 +1,81 @@
+//! Deduplication of retried requests by `Idempotency-Key`
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Outcome of claiming a key.
+pub enum Claim<T> {
+    /// First use of the key; the caller must `complete` or `release` it.
+    New,
+    /// Another request with this key is still running.
+    InProgress,
+    /// A request with this key already finished with this response.
+    Completed(T),
+    /// The key was first used with a different request body.
+    Mismatch,
+}
+
+enum Slot<T> {
+    Pending,
+    Done(T),
+}
+
+struct Entry<T> {
+    claimed_at: Instant,
+    /// Hash of the request body the key was first used with.
+    body_hash: String,
+    slot: Slot<T>,
+}
+
+/// Remembers the response for each key for `ttl` after it was claimed.
+pub struct IdempotencyStore<T> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry<T>>>,
+}
+
+impl<T: Clone> IdempotencyStore<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claims `key` for a request whose body hashes to `body_hash`. Reusing
+    /// a key with a different body gives [`Claim::Mismatch`].
+    pub fn claim(&self, key: &str, body_hash: &str) -> Claim<T> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        entries.retain(|_, entry| now.duration_since(entry.claimed_at) < self.ttl);
+
+        match entries.get(key) {
+            Some(entry) if entry.body_hash != body_hash => Claim::Mismatch,
+            Some(Entry { slot: Slot::Pending, .. }) => Claim::InProgress,
+            Some(Entry { slot: Slot::Done(response), .. }) => Claim::Completed(response.clone()),
+            None => {
+                let entry = Entry {
+                    claimed_at: now,
+                    body_hash: body_hash.to_string(),
+                    slot: Slot::Pending,
+                };
+                entries.insert(key.to_string(), entry);
+                Claim::New
+            }
+        }
+    }
+
+    /// Stores the response for a key claimed with [`Claim::New`].
+    pub fn complete(&self, key: &str, response: T) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.get_mut(key) {
+            entry.slot = Slot::Done(response);
+        }
+    }
+
+    /// Forgets a claimed key so a retry runs the request again.
+    pub fn release(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.remove(key);
+    }
+}
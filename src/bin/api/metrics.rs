@@ -0,0 +1,123 @@
+//! Prometheus instrumentation for the API server
+
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+
+pub struct Metrics {
+    registry: Registry,
+    pub scans_total: IntCounter,
+    pub scan_duration_seconds: Histogram,
+    pub queries_total: IntCounter,
+    pub query_duration_seconds: Histogram,
+    pub analyses_total: IntCounter,
+    pub analysis_duration_seconds: Histogram,
+    pub rate_limit_remaining: IntGauge,
+    pub errors_total: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new();
+
+        let scans_total = IntCounter::new("cadsp_scans_total", "Repository scans requested")?;
+        let scan_duration_seconds = Histogram::with_opts(HistogramOpts::new(
+            "cadsp_scan_duration_seconds",
+            "Repository scan duration in seconds",
+        ))?;
+        let queries_total = IntCounter::new("cadsp_queries_total", "Engine path queries requested")?;
+        let query_duration_seconds = Histogram::with_opts(HistogramOpts::new(
+            "cadsp_query_duration_seconds",
+            "Engine path query latency in seconds",
+        ))?;
+        let analyses_total = IntCounter::new(
+            "cadsp_analyses_total",
+            "Code analysis requests, single or batch",
+        )?;
+        let analysis_duration_seconds = Histogram::with_opts(HistogramOpts::new(
+            "cadsp_analysis_duration_seconds",
+            "Code analysis request latency in seconds",
+        ))?;
+        let rate_limit_remaining = IntGauge::new(
+            "cadsp_github_rate_limit_remaining",
+            "Remaining GitHub API requests in the current rate-limit window",
+        )?;
+        let errors_total = IntCounterVec::new(
+            Opts::new("cadsp_errors_total", "Errors by CADSPError variant"),
+            &["kind"],
+        )?;
+
+        registry.register(Box::new(scans_total.clone()))?;
+        registry.register(Box::new(scan_duration_seconds.clone()))?;
+        registry.register(Box::new(queries_total.clone()))?;
+        registry.register(Box::new(query_duration_seconds.clone()))?;
+        registry.register(Box::new(analyses_total.clone()))?;
+        registry.register(Box::new(analysis_duration_seconds.clone()))?;
+        registry.register(Box::new(rate_limit_remaining.clone()))?;
+        registry.register(Box::new(errors_total.clone()))?;
+
+        Ok(Self {
+            registry,
+            scans_total,
+            scan_duration_seconds,
+            queries_total,
+            query_duration_seconds,
+            analyses_total,
+            analysis_duration_seconds,
+            rate_limit_remaining,
+            errors_total,
+        })
+    }
+
+    /// Counts an error under its `CADSPError` variant name.
+    pub fn record_error(&self, error: &cadsp_core::CADSPError) {
+        self.errors_total.with_label_values(&[&error_kind(error)]).inc();
+    }
+
+    /// Renders all registered metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!("Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+/// Variant name of an error, taken from its `Debug` form (`Variant(..)` or
+/// `Variant { .. }`).
+fn error_kind(error: &cadsp_core::CADSPError) -> String {
+    format!("{:?}", error)
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+/// Refreshes the rate-limit gauge from GitHub's `/rate_limit` endpoint, which
+/// does not itself count against the limit.
+pub async fn refresh_rate_limit(metrics: &Metrics, github_token: &str) {
+    let mut request = reqwest::Client::new()
+        .get("https://api.github.com/rate_limit")
+        .header("User-Agent", "cadsp-api");
+    if !github_token.is_empty() {
+        request = request.bearer_auth(github_token);
+    }
+
+    let remaining = match request.send().await {
+        Ok(response) => response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| body["resources"]["core"]["remaining"].as_i64()),
+        Err(e) => {
+            tracing::warn!("Failed to refresh GitHub rate limit: {}", e);
+            None
+        }
+    };
+
+    if let Some(remaining) = remaining {
+        metrics.rate_limit_remaining.set(remaining);
+    }
+}
@@ -0,0 +1,53 @@
+//! Uniform paging for endpoints that return collections
+
+use serde::{Deserialize, Serialize};
+
+/// Page size when the request gives no `limit`.
+pub const DEFAULT_LIMIT: usize = 50;
+/// Largest page a request may ask for; bigger limits are clamped.
+pub const MAX_LIMIT: usize = 500;
+
+/// `?limit=&offset=` query parameters.
+#[derive(Debug, Default, Deserialize)]
+pub struct PageParams {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl PageParams {
+    /// The requested limit, defaulted and clamped to `1..=MAX_LIMIT`.
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset.unwrap_or(0)
+    }
+}
+
+/// One page of a collection. `next_offset` is absent on the last page.
+#[derive(Debug, Serialize)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+    pub next_offset: Option<usize>,
+}
+
+impl<T> Paginated<T> {
+    /// Cuts the page described by `params` out of the full collection.
+    pub fn from_items(items: Vec<T>, params: &PageParams) -> Self {
+        let (limit, offset) = (params.limit(), params.offset());
+        let total = items.len();
+        let items: Vec<T> = items.into_iter().skip(offset).take(limit).collect();
+        let end = offset.saturating_add(items.len());
+        Self {
+            items,
+            total,
+            limit,
+            offset,
+            next_offset: (end < total).then_some(end),
+        }
+    }
+}
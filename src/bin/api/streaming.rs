@@ -0,0 +1,47 @@
+//! Adapter for streaming blocking writers into a response body
+
+use axum::body::{Body, Bytes};
+use std::io::{self, Write};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Chunks queued between the writer thread and the response.
+const CHUNK_QUEUE: usize = 16;
+
+/// A `Write` that forwards every write as one body chunk. Writes fail with
+/// `BrokenPipe` once the client has gone away, which stops the producer.
+pub struct ChunkWriter {
+    sender: mpsc::Sender<io::Result<Bytes>>,
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sender
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "response body dropped"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs `produce` on the blocking thread pool and streams what it writes
+/// as the body. Output is buffered into chunks of about `chunk_size`
+/// bytes. A failure after the first chunk aborts the body mid-stream.
+pub fn stream_body<F>(chunk_size: usize, produce: F) -> Body
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(CHUNK_QUEUE);
+    let errors = sender.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut out = io::BufWriter::with_capacity(chunk_size, ChunkWriter { sender });
+        if let Err(e) = produce(&mut out).and_then(|()| out.flush()) {
+            tracing::warn!(error = %e, "Streaming response aborted");
+            let _ = errors.blocking_send(Err(e));
+        }
+    });
+    Body::from_stream(ReceiverStream::new(receiver))
+}
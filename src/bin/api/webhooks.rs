@@ -0,0 +1,63 @@
+//! GitHub webhook handling
+
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Deserialize)]
+pub struct PushRepository {
+    pub html_url: String,
+    pub full_name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PushCommit {
+    #[serde(default)]
+    pub added: Vec<String>,
+    #[serde(default)]
+    pub modified: Vec<String>,
+    #[serde(default)]
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PushEvent {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub repository: PushRepository,
+    #[serde(default)]
+    pub commits: Vec<PushCommit>,
+}
+
+impl PushEvent {
+    /// Paths touched by any commit in the push, deduplicated and sorted.
+    pub fn changed_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self
+            .commits
+            .iter()
+            .flat_map(|c| c.added.iter().chain(&c.modified).chain(&c.removed))
+            .cloned()
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+    }
+}
+
+/// Checks an `X-Hub-Signature-256` header (`sha256=<hex>`) against the HMAC
+/// of `body` under `secret`, in constant time.
+pub fn verify_signature(secret: &str, body: &[u8], signature_header: &str) -> bool {
+    let Some(hex_signature) = signature_header.strip_prefix("sha256=") else {
+        return false;
+    };
+    let Ok(signature) = hex::decode(hex_signature) else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
@@ -3,9 +3,69 @@
 
 use std::io::{self, Write};
 use cadsp_core::*;
+use neuro_node_path_engine::{
+    repository::CodeRepository, EngineConfig, KnotenlexikonStore, NeuroNodePathEngine,
+};
 
 #[tokio::main]
 async fn main() {
+    init_tracing();
+
+    let options = match CliOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("✗ {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    match options.args.first().map(String::as_str) {
+        // `cadsp glossary` prints the lexicon as Markdown and exits, so it
+        // can be redirected into a file without the banner.
+        Some("glossary") => {
+            print!("{}", KnotenlexikonStore::new().to_markdown());
+            return;
+        }
+        // `cadsp health [path]` indexes a local checkout and prints its
+        // health score, for tracking over time.
+        Some("health") => {
+            if let Err(e) = print_health(options.path(), options.max_depth).await {
+                eprintln!("✗ Health check failed: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        // `cadsp index [path]` indexes a local checkout and prints what
+        // was indexed.
+        Some("index") => {
+            let result = async {
+                let mut engine = new_engine().await?;
+                index_path(&mut engine, options.path(), options.max_depth).await
+            };
+            if let Err(e) = result.await {
+                eprintln!("✗ Indexing failed: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        // `cadsp query <path> <text>` indexes a local checkout and answers
+        // one query against it, in the `--lang` language.
+        Some("query") => {
+            let text = options.args.get(2..).unwrap_or_default().join(" ");
+            let result = async {
+                let mut engine = new_engine().await?;
+                index_path(&mut engine, options.path(), options.max_depth).await?;
+                run_query(&engine, &text, &options.language).await
+            };
+            if let Err(e) = result.await {
+                eprintln!("✗ Query failed: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        _ => {}
+    }
+
     println!();
     println!("╔════════════════════════════════════════════════════════════╗");
     println!("║   🧠 CADSP: Cybernetic AI Design Synthesis Platform       ║");
@@ -20,12 +80,22 @@ async fn main() {
         });
 
     let scanner = RepositoryScanner::new(github_token);
+    let mut engine = match new_engine().await {
+        Ok(engine) => engine,
+        Err(e) => {
+            eprintln!("✗ Failed to initialize engine: {}", e);
+            std::process::exit(1);
+        }
+    };
 
     loop {
         println!();
         println!("Commands:");
         println!("  scan <url>     - Scan a GitHub repository");
         println!("  analyze <code> - Analyze code for biophysical patterns");
+        println!("  index <path>   - Index a local checkout into this session");
+        println!("  query <text>   - Query the indexed checkouts in --lang");
+        println!("  glossary       - Print the Knotenlexikon as Markdown");
         println!("  help           - Show this help");
         println!("  exit           - Exit CADSP");
         println!();
@@ -52,6 +122,12 @@ async fn main() {
                 println!("CADSP Help");
                 println!("  scan <url>  - Scan GitHub repository at <url>");
                 println!("  analyze     - Analyze code patterns");
+                println!("  index <path> - Index a local checkout into this session");
+                println!("  query <text> - Query the session; answered in --lang (default en)");
+                println!("  glossary    - Print the Knotenlexikon as Markdown");
+            }
+            "glossary" => {
+                print!("{}", KnotenlexikonStore::new().to_markdown());
             }
             s if s.starts_with("scan ") => {
                 let url = s.trim_start_matches("scan ").trim();
@@ -68,9 +144,159 @@ async fn main() {
                     Err(e) => println!("✗ Scan failed: {}", e),
                 }
             }
+            s if s.starts_with("index ") => {
+                let path = s.trim_start_matches("index ").trim();
+                if let Err(e) = index_path(&mut engine, path, options.max_depth).await {
+                    println!("✗ Indexing failed: {}", e);
+                }
+            }
+            s if s.starts_with("query ") => {
+                let text = s.trim_start_matches("query ").trim();
+                if let Err(e) = run_query(&engine, text, &options.language).await {
+                    println!("✗ Query failed: {}", e);
+                }
+            }
             _ => {
                 println!("Unknown command. Try 'help' or 'exit'.");
             }
         }
     }
 }
+
+/// Command-line arguments with the flags taken out. Flags apply to every
+/// command that indexes, including `index` in the interactive session.
+struct CliOptions {
+    /// The subcommand and its arguments.
+    args: Vec<String>,
+    /// `--max-dir-depth N`: only index files at most N directory levels
+    /// below the repository root.
+    max_depth: Option<usize>,
+    /// `--lang <en|de>`: language queries are answered and explained in.
+    language: String,
+}
+
+impl CliOptions {
+    fn parse(mut argv: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut options = CliOptions {
+            args: Vec::new(),
+            max_depth: None,
+            language: "en".to_string(),
+        };
+        while let Some(arg) = argv.next() {
+            if arg == "--max-dir-depth" {
+                let value = argv
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--max-dir-depth needs a value"))?;
+                let depth: usize = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid --max-dir-depth '{}'", value))?;
+                options.max_depth = Some(depth);
+            } else if arg == "--lang" {
+                options.language = argv
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--lang needs a value"))?;
+            } else {
+                options.args.push(arg);
+            }
+        }
+        Ok(options)
+    }
+
+    /// The repository path after the subcommand, `.` by default.
+    fn path(&self) -> &str {
+        self.args.get(1).map(String::as_str).unwrap_or(".")
+    }
+}
+
+/// Logs as JSON when `LOG_FORMAT=json`, otherwise as human-readable text.
+/// Logs go to stderr, so redirected command output stays clean.
+fn init_tracing() {
+    let builder = tracing_subscriber::fmt().with_writer(io::stderr);
+    match std::env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => builder.json().init(),
+        _ => builder.init(),
+    }
+}
+
+/// A fresh engine, warmed so a broken built-in pattern fails here rather
+/// than in the middle of an index.
+async fn new_engine() -> anyhow::Result<NeuroNodePathEngine> {
+    let engine = NeuroNodePathEngine::new(EngineConfig::default())?;
+    engine.warm().await?;
+    Ok(engine)
+}
+
+/// Opens the repository at `path`, limited to `max_depth` directory levels.
+fn open_repository(path: &str, max_depth: Option<usize>) -> anyhow::Result<CodeRepository> {
+    let repository = CodeRepository::new(std::path::PathBuf::from(path))?;
+    Ok(match max_depth {
+        Some(depth) => repository.with_max_depth(depth),
+        None => repository,
+    })
+}
+
+/// Indexes the repository at `path` into `engine` and prints the report.
+async fn index_path(
+    engine: &mut NeuroNodePathEngine,
+    path: &str,
+    max_depth: Option<usize>,
+) -> anyhow::Result<()> {
+    let repository = open_repository(path, max_depth)?;
+    println!("📂 Indexing: {}", path);
+    let report = engine.index_repository(&repository).await?;
+    if report.unchanged {
+        println!("✓ Unchanged since the last index");
+        return Ok(());
+    }
+    println!("✓ Indexed {} files", report.files_indexed);
+    if !report.lossy_files.is_empty() {
+        println!("  Decoded lossily: {}", report.lossy_files.len());
+    }
+    if !report.unreadable_files.is_empty() {
+        println!("  Unreadable: {}", report.unreadable_files.len());
+    }
+    if report.files_skipped > 0 {
+        println!("  Below --max-dir-depth: {}", report.files_skipped);
+    }
+    Ok(())
+}
+
+/// Answers `text` in `language` and prints the path with its explanation.
+/// Unsupported languages fall back to the engine's default, with a note.
+async fn run_query(
+    engine: &NeuroNodePathEngine,
+    text: &str,
+    language: &str,
+) -> anyhow::Result<()> {
+    let result = engine.query_in(text, language).await?;
+    if let Some(note) = &result.language_note {
+        println!("⚠️  {}", note);
+    }
+    println!("✓ {} nodes on the path", result.total_nodes);
+    for node_id in &result.node_path {
+        let name = engine.get_node(node_id)?.map(|node| node.name);
+        println!("  {}", name.as_deref().unwrap_or(node_id));
+    }
+    println!("{}", result.explanation);
+    Ok(())
+}
+
+/// Indexes the repository at `path` and prints its health score with the
+/// score of each component. Files deeper than `max_depth` directory levels
+/// are left out.
+async fn print_health(path: &str, max_depth: Option<usize>) -> anyhow::Result<()> {
+    let mut engine = new_engine().await?;
+    let report = engine.index_repository(&open_repository(path, max_depth)?).await?;
+    if report.files_skipped > 0 {
+        println!("Skipped {} files below --max-dir-depth", report.files_skipped);
+    }
+
+    let health = engine.health_score()?;
+    println!("Health score: {:.1} / 100", health.value);
+    let mut components: Vec<_> = health.components.into_iter().collect();
+    components.sort_by(|a, b| a.0.cmp(&b.0));
+    for (name, score) in components {
+        println!("  {:<10} {:>5.1}", name, score);
+    }
+    Ok(())
+}